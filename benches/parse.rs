@@ -0,0 +1,92 @@
+// benches/parse.rs
+//! Times `DerParser::parse_all` and `parse_owned` with criterion, and prints
+//! the heap allocations each makes per call. Run with `cargo bench --bench parse`.
+use asn1smith::der_parser::{ASN1Object, DerParser, OwnedObject, parse_owned, try_decode_input};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Forwards to the system allocator, counting every allocation.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Prints how many allocations one call of `parse` makes.
+fn print_allocations<T>(name: &str, parse: impl FnOnce() -> T) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(parse());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    println!("{}: {} allocs/iter", name, allocations);
+}
+
+fn bench<T>(c: &mut Criterion, name: &str, mut parse: impl FnMut() -> T) {
+    print_allocations(name, &mut parse);
+    c.bench_function(name, |b| b.iter(&mut parse));
+}
+
+fn borrowed(input: &[u8]) -> Vec<ASN1Object<'_>> {
+    DerParser::new(black_box(input)).parse_all().unwrap()
+}
+
+fn small_inputs(c: &mut Criterion) {
+    let cert = try_decode_input(include_str!("../testCert.pem")).unwrap();
+    bench(c, "certificate", || borrowed(&cert));
+
+    let many_certs = cert.repeat(100);
+    bench(c, "100 certificates", || borrowed(&many_certs));
+
+    // 100 levels of SEQUENCE { ... INTEGER 0 }, just under the depth limit.
+    let mut nested = vec![0x02, 0x01, 0x00];
+    for _ in 0..100 {
+        let mut outer = vec![0x30];
+        outer.extend(asn1smith::der_encoder::encode_length(nested.len()));
+        outer.extend(nested);
+        nested = outer;
+    }
+    bench(c, "100 nested SEQUENCEs", || borrowed(&nested));
+}
+
+/// Owned trees for a multi-megabyte input, as the TUI builds on every parse:
+/// converting a borrowed tree afterwards, and `parse_owned`, which builds
+/// owned nodes directly and skips the intermediate borrowed tree.
+fn large_input(c: &mut Criterion) {
+    let cert = try_decode_input(include_str!("../testCert.pem")).unwrap();
+    let large = cert.repeat(2_000);
+    let convert = || {
+        borrowed(&large)
+            .iter()
+            .map(OwnedObject::from)
+            .collect::<Vec<_>>()
+    };
+    let owned = || parse_owned(black_box(&large)).unwrap();
+    print_allocations("borrowed + From", convert);
+    print_allocations("parse_owned", owned);
+    let mut group = c.benchmark_group(format!("{} byte input", large.len()));
+    group.sample_size(20);
+    group.bench_function("borrowed + From", |b| b.iter(convert));
+    group.bench_function("parse_owned", |b| b.iter(owned));
+    group.finish();
+}
+
+criterion_group!(benches, small_inputs, large_input);
+criterion_main!(benches);
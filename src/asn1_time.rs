@@ -0,0 +1,287 @@
+// src/asn1_time.rs
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar timestamp decoded from a UTCTime or GeneralizedTime value,
+/// normalized to UTC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub struct Asn1Time {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+impl Asn1Time {
+    /// Formats the timestamp as ISO-8601, e.g. `2023-01-15T12:00:00Z`.
+    /// Fractional seconds are included only when present.
+    pub fn to_iso8601(&self) -> String {
+        let fraction = if self.nanosecond == 0 {
+            String::new()
+        } else {
+            let digits = format!("{:09}", self.nanosecond);
+            format!(".{}", digits.trim_end_matches('0'))
+        };
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second, fraction
+        )
+    }
+
+    /// Seconds since the Unix epoch (negative for earlier dates).
+    pub fn unix_seconds(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * 86_400
+            + self.hour as i64 * 3_600
+            + self.minute as i64 * 60
+            + self.second as i64
+    }
+
+    fn from_unix_seconds(seconds: i64, nanosecond: u32) -> Self {
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        let secs_of_day = seconds.rem_euclid(86_400) as u32;
+        Asn1Time {
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3_600,
+            minute: secs_of_day / 60 % 60,
+            second: secs_of_day % 60,
+            nanosecond,
+        }
+    }
+}
+
+/// The RFC 5280 rule for UTCTime's two-digit years: 50–99 are 19xx and
+/// 00–49 are 20xx.
+pub const RFC5280_YEAR_PIVOT: u32 = 50;
+
+/// Parses the contents of a UTCTime (`YYMMDDHHMM[SS]`) or GeneralizedTime
+/// (`YYYYMMDDHHMMSS[.fff]`) value followed by `Z` or a `+hhmm`/`-hhmm` offset.
+/// Offsets are folded into the result so it is always in UTC. Two-digit years
+/// follow RFC 5280: 50–99 are 19xx and 00–49 are 20xx.
+pub fn parse_asn1_time(bytes: &[u8], is_generalized: bool) -> Option<Asn1Time> {
+    parse_asn1_time_with_pivot(bytes, is_generalized, RFC5280_YEAR_PIVOT)
+}
+
+/// Like [`parse_asn1_time`], but two-digit UTCTime years from `pivot` up are
+/// 19xx and those below it are 20xx (so 0 makes every year 19xx and 100
+/// every year 20xx).
+pub fn parse_asn1_time_with_pivot(
+    bytes: &[u8],
+    is_generalized: bool,
+    pivot: u32,
+) -> Option<Asn1Time> {
+    let text = std::str::from_utf8(bytes).ok().filter(|t| t.is_ascii())?;
+    let (body, offset_minutes) = match text.strip_suffix('Z') {
+        Some(body) => (body, 0),
+        None => {
+            let (body, zone) = text.split_at(text.len().checked_sub(5)?);
+            let sign = match zone.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let zone = &zone[1..];
+            if !zone.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let hh: i64 = zone[..2].parse().ok()?;
+            let mm: i64 = zone[2..].parse().ok()?;
+            if hh >= 24 || mm >= 60 {
+                return None;
+            }
+            (body, sign * (hh * 60 + mm))
+        }
+    };
+    // Only GeneralizedTime may carry fractional seconds, after a '.' or ','.
+    let (digits, fraction) = match body.find(['.', ',']) {
+        Some(i) if is_generalized => (&body[..i], Some(&body[i + 1..])),
+        Some(_) => return None,
+        None => (body, None),
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let field =
+        |start: usize, len: usize| -> Option<u32> { digits.get(start..start + len)?.parse().ok() };
+
+    let (year, rest) = if is_generalized {
+        (field(0, 4)? as i32, 4)
+    } else {
+        let yy = field(0, 2)?;
+        let century = if yy >= pivot { 1900 } else { 2000 };
+        (century + yy as i32, 2)
+    };
+    let seconds_present = match digits.len() - rest {
+        10 => true,
+        8 if !is_generalized => false,
+        _ => return None,
+    };
+    let nanosecond = match fraction {
+        None => 0,
+        Some(f) if f.is_empty() || f.len() > 9 || !f.bytes().all(|b| b.is_ascii_digit()) => {
+            return None;
+        }
+        Some(f) => format!("{:0<9}", f).parse().ok()?,
+    };
+
+    let time = Asn1Time {
+        year,
+        month: field(rest, 2)?,
+        day: field(rest + 2, 2)?,
+        hour: field(rest + 4, 2)?,
+        minute: field(rest + 6, 2)?,
+        second: if seconds_present {
+            field(rest + 8, 2)?
+        } else {
+            0
+        },
+        nanosecond,
+    };
+    let valid = (1..=12).contains(&time.month)
+        && (1..=days_in_month(time.year, time.month)).contains(&time.day)
+        && time.hour < 24
+        && time.minute < 60
+        && time.second < 60;
+    if !valid {
+        return None;
+    }
+    if offset_minutes == 0 {
+        return Some(time);
+    }
+    Some(Asn1Time::from_unix_seconds(
+        time.unix_seconds() - offset_minutes * 60,
+        nanosecond,
+    ))
+}
+
+/// Current wall-clock time as seconds since the Unix epoch.
+pub fn now_unix_seconds() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Number of days in `month` of `year`, counting February 29 in leap years.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year } as i64;
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_utc_time() {
+        let time = parse_asn1_time(b"230115120000Z", false).unwrap();
+        assert_eq!(time.to_iso8601(), "2023-01-15T12:00:00Z");
+        assert_eq!(time.unix_seconds(), 1_673_784_000);
+    }
+
+    #[test]
+    fn test_parse_utc_time_without_seconds_and_old_century() {
+        let time = parse_asn1_time(b"9912312359Z", false).unwrap();
+        assert_eq!(time.to_iso8601(), "1999-12-31T23:59:00Z");
+    }
+
+    #[test]
+    fn test_utc_time_year_pivot() {
+        let year = |bytes: &[u8], pivot| {
+            parse_asn1_time_with_pivot(bytes, false, pivot)
+                .unwrap()
+                .year
+        };
+        assert_eq!(parse_asn1_time(b"490101000000Z", false).unwrap().year, 2049);
+        assert_eq!(parse_asn1_time(b"500101000000Z", false).unwrap().year, 1950);
+        assert_eq!(year(b"500101000000Z", 60), 2050);
+        assert_eq!(year(b"000101000000Z", 0), 1900);
+        assert_eq!(year(b"990101000000Z", 100), 2099);
+        // GeneralizedTime has a four-digit year and ignores the pivot.
+        let time = parse_asn1_time_with_pivot(b"19500101000000Z", true, 60).unwrap();
+        assert_eq!(time.year, 1950);
+    }
+
+    #[test]
+    fn test_parse_generalized_time() {
+        let time = parse_asn1_time(b"20500101000000Z", true).unwrap();
+        assert_eq!(time.to_iso8601(), "2050-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_parse_offset_normalizes_to_utc() {
+        let time = parse_asn1_time(b"20230115140000+0200", true).unwrap();
+        assert_eq!(time.to_iso8601(), "2023-01-15T12:00:00Z");
+        let time = parse_asn1_time(b"2301151400+0200", false).unwrap();
+        assert_eq!(time.to_iso8601(), "2023-01-15T12:00:00Z");
+        // Crossing midnight and a year boundary.
+        let time = parse_asn1_time(b"20230101003000+0100", true).unwrap();
+        assert_eq!(time.to_iso8601(), "2022-12-31T23:30:00Z");
+        let time = parse_asn1_time(b"20221231230000-0130", true).unwrap();
+        assert_eq!(time.to_iso8601(), "2023-01-01T00:30:00Z");
+    }
+
+    #[test]
+    fn test_parse_fractional_seconds() {
+        let time = parse_asn1_time(b"20230115120000.25Z", true).unwrap();
+        assert_eq!(time.nanosecond, 250_000_000);
+        assert_eq!(time.to_iso8601(), "2023-01-15T12:00:00.25Z");
+        let time = parse_asn1_time(b"20230115140000,5+0200", true).unwrap();
+        assert_eq!(time.to_iso8601(), "2023-01-15T12:00:00.5Z");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert_eq!(parse_asn1_time(b"230115120000", false), None);
+        assert_eq!(parse_asn1_time(b"231315120000Z", false), None);
+        assert_eq!(parse_asn1_time(b"2023011512Z", true), None);
+        assert_eq!(parse_asn1_time(b"230115120000.5Z", false), None);
+        assert_eq!(parse_asn1_time(b"20230115120000.Z", true), None);
+        assert_eq!(parse_asn1_time(b"20230115120000+2400", true), None);
+        assert_eq!(parse_asn1_time(b"20230115120000*0200", true), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_days_past_end_of_month() {
+        assert_eq!(parse_asn1_time(b"230431120000Z", false), None);
+        assert_eq!(parse_asn1_time(b"230229120000Z", false), None);
+        assert_eq!(parse_asn1_time(b"21000229120000Z", true), None);
+        let leap_day = parse_asn1_time(b"240229120000Z", false).unwrap();
+        assert_eq!(leap_day.to_iso8601(), "2024-02-29T12:00:00Z");
+        assert!(parse_asn1_time(b"20000229120000Z", true).is_some());
+        assert!(parse_asn1_time(b"230131120000Z", false).is_some());
+    }
+}
@@ -0,0 +1,265 @@
+// src/decode.rs
+
+/// Decodes a BMPString (UCS-2, big-endian UTF-16) into text.
+/// Returns `None` for odd-length input or unpaired surrogates.
+pub fn decode_bmp_string(bytes: &[u8]) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+/// Whether `c` is in the PrintableString alphabet (X.680 41.4): Latin
+/// letters, digits, space and `'()+,-./:=?`.
+pub fn is_printable_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || " '()+,-./:=?".contains(c)
+}
+
+/// Decodes the content of the restricted character string types that have
+/// no dedicated decoder: NumericString, VisibleString and GeneralString must
+/// be ASCII; UniversalString is UTF-32BE; GraphicString, VideotexString and
+/// ObjectDescriptor are shown as UTF-8 when valid and Latin-1 otherwise.
+/// Returns `None` for other tag numbers or undecodable content.
+pub fn decode_restricted_string(number: u32, bytes: &[u8]) -> Option<String> {
+    match number {
+        18 | 26 | 27 => bytes
+            .is_ascii()
+            .then(|| String::from_utf8_lossy(bytes).into_owned()),
+        28 => {
+            if !bytes.len().is_multiple_of(4) {
+                return None;
+            }
+            bytes
+                .chunks_exact(4)
+                .map(|c| char::from_u32(u32::from_be_bytes([c[0], c[1], c[2], c[3]])))
+                .collect()
+        }
+        7 | 21 | 25 => Some(match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) => bytes.iter().map(|&b| b as char).collect(),
+        }),
+        _ => None,
+    }
+}
+
+/// Decodes REAL content octets (X.690 8.5): the binary form with base 2, 8
+/// or 16, the ISO 6093 decimal forms (NR1-NR3), and the one-octet special
+/// values. Binary values beyond `f64` range become infinity or zero. Returns
+/// `None` for reserved encodings or malformed content.
+pub fn decode_real(bytes: &[u8]) -> Option<f64> {
+    let Some((&first, rest)) = bytes.split_first() else {
+        return Some(0.0);
+    };
+    if first & 0x80 != 0 {
+        let log2_base = match (first >> 4) & 0x03 {
+            0 => 1,
+            1 => 3,
+            2 => 4,
+            _ => return None,
+        };
+        let scale = i64::from((first >> 2) & 0x03);
+        let (exponent_bytes, mantissa_bytes) = match first & 0x03 {
+            3 => {
+                let (&len, rest) = rest.split_first()?;
+                rest.split_at_checked(len as usize)?
+            }
+            n => rest.split_at_checked(n as usize + 1)?,
+        };
+        if exponent_bytes.is_empty() || exponent_bytes.len() > 8 || mantissa_bytes.is_empty() {
+            return None;
+        }
+        let exponent = exponent_bytes[1..]
+            .iter()
+            .fold(i64::from(exponent_bytes[0] as i8), |acc, &b| {
+                (acc << 8) | i64::from(b)
+            });
+        let mantissa = mantissa_bytes
+            .iter()
+            .fold(0.0f64, |acc, &b| acc * 256.0 + f64::from(b));
+        let power = exponent
+            .saturating_mul(log2_base)
+            .saturating_add(scale)
+            .clamp(-2200, 2200) as i32;
+        let value = mantissa * 2f64.powi(power);
+        return Some(if first & 0x40 != 0 { -value } else { value });
+    }
+    if first & 0x40 != 0 {
+        return match (first, rest) {
+            (0x40, []) => Some(f64::INFINITY),
+            (0x41, []) => Some(f64::NEG_INFINITY),
+            (0x42, []) => Some(f64::NAN),
+            (0x43, []) => Some(-0.0),
+            _ => None,
+        };
+    }
+    // Decimal: ISO 6093 NR1/NR2/NR3 text, which may use a comma as the
+    // decimal mark and be padded with leading spaces.
+    if !(1..=3).contains(&first) {
+        return None;
+    }
+    let text = std::str::from_utf8(rest).ok()?.trim_start_matches(' ');
+    text.replace(',', ".").parse().ok()
+}
+
+/// Formats a decoded REAL, naming the special values as X.680 does.
+pub fn format_real(value: f64) -> String {
+    if value.is_nan() {
+        "NOT-A-NUMBER".to_string()
+    } else if value == f64::INFINITY {
+        "PLUS-INFINITY".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "MINUS-INFINITY".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// BIT STRINGs with at most this many data bytes are small enough (e.g.
+/// KeyUsage) for a list of set bits to be more useful than the raw bytes.
+pub const NAMED_BITS_MAX_BYTES: usize = 4;
+
+/// Splits BIT STRING content into its unused-bit count and the indices of the
+/// set bits, where bit 0 is the most significant bit of the first data byte
+/// (X.680). Returns `None` for an invalid unused-bit count.
+pub fn bit_string_bits(bytes: &[u8]) -> Option<(u8, Vec<usize>)> {
+    let (&unused, data) = bytes.split_first()?;
+    if unused > 7 || (data.is_empty() && unused != 0) {
+        return None;
+    }
+    let total_bits = data.len() * 8 - unused as usize;
+    let set = (0..total_bits)
+        .filter(|&i| data[i / 8] & (0x80 >> (i % 8)) != 0)
+        .collect();
+    Some((unused, set))
+}
+
+/// Formats bit indices as a set, e.g. `{0, 5}`.
+pub fn format_bit_set(bits: &[usize]) -> String {
+    let items: Vec<String> = bits.iter().map(|b| b.to_string()).collect();
+    format!("{{{}}}", items.join(", "))
+}
+
+/// Formats ENUMERATED content, which is encoded like an INTEGER, as its
+/// signed value followed by the value's name in `names` if it has one, e.g.
+/// `1 (keyCompromise)` for a CRLReason. Callers that don't know which
+/// enumeration a value belongs to pass no names.
+pub fn format_enumerated(bytes: &[u8], names: &[(i64, &str)]) -> String {
+    let value = num_bigint::BigInt::from_signed_bytes_be(bytes);
+    let name = names
+        .iter()
+        .find(|&&(n, _)| num_bigint::BigInt::from(n) == value)
+        .map(|&(_, name)| name);
+    match name {
+        Some(name) => format!("{} ({})", value, name),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_enumerated() {
+        assert_eq!(format_enumerated(&[0x01], &[]), "1");
+        assert_eq!(format_enumerated(&[0xFF], &[]), "-1");
+        let reasons = [(0, "unspecified"), (1, "keyCompromise")];
+        assert_eq!(format_enumerated(&[0x01], &reasons), "1 (keyCompromise)");
+        assert_eq!(format_enumerated(&[0x05], &reasons), "5");
+    }
+
+    #[test]
+    fn test_bit_string_bits() {
+        // KeyUsage digitalSignature + keyEncipherment: 03 02 05 A0
+        assert_eq!(bit_string_bits(&[0x05, 0xA0]), Some((5, vec![0, 2])));
+        assert_eq!(format_bit_set(&[0, 2]), "{0, 2}");
+        // Padding bits are ignored even if set.
+        assert_eq!(bit_string_bits(&[0x01, 0x03]), Some((1, vec![6])));
+        assert_eq!(bit_string_bits(&[0x00, 0x00, 0x01]), Some((0, vec![15])));
+        assert_eq!(bit_string_bits(&[0x00]), Some((0, vec![])));
+    }
+
+    #[test]
+    fn test_bit_string_bits_invalid() {
+        assert_eq!(bit_string_bits(&[]), None);
+        assert_eq!(bit_string_bits(&[0x08, 0xFF]), None);
+        assert_eq!(bit_string_bits(&[0x03]), None);
+    }
+
+    #[test]
+    fn test_decode_bmp_string() {
+        assert_eq!(
+            decode_bmp_string(&[0x00, b'U', 0x00, b's', 0x00, b'e', 0x00, b'r']),
+            Some("User".to_string())
+        );
+        assert_eq!(
+            decode_bmp_string(&[0x00, 0xE9, 0x4E, 0x2D]),
+            Some("é中".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_bmp_string_invalid() {
+        assert_eq!(decode_bmp_string(&[0x00, b'U', 0x00]), None);
+        assert_eq!(decode_bmp_string(&[0xD8, 0x00]), None);
+    }
+
+    #[test]
+    fn test_decode_real_binary() {
+        // 5 * 2^-5
+        assert_eq!(decode_real(&[0x80, 0xFB, 0x05]), Some(0.15625));
+        // Negative, base 16, scale factor 1: -(3 * 2^1 * 16^1)
+        assert_eq!(decode_real(&[0xE4, 0x01, 0x03]), Some(-96.0));
+        // Two-octet exponent 0x0100 and a two-octet mantissa: 1 * 2^256
+        assert_eq!(decode_real(&[0x81, 0x01, 0x00, 0x01]), Some(2f64.powi(256)));
+        // Exponent length given in its own octet.
+        assert_eq!(decode_real(&[0x83, 0x01, 0x02, 0x03]), Some(12.0));
+        // Base 8: 1 * 8^2
+        assert_eq!(decode_real(&[0x90, 0x02, 0x01]), Some(64.0));
+        // Reserved base, and a missing mantissa.
+        assert_eq!(decode_real(&[0xB0, 0x00, 0x01]), None);
+        assert_eq!(decode_real(&[0x80, 0x00]), None);
+    }
+
+    #[test]
+    fn test_decode_real_decimal_and_special() {
+        assert_eq!(decode_real(&[]), Some(0.0));
+        assert_eq!(decode_real(b"\x01  -42"), Some(-42.0));
+        assert_eq!(decode_real(b"\x021,5"), Some(1.5));
+        assert_eq!(decode_real(b"\x0325E-2"), Some(0.25));
+        assert_eq!(decode_real(b"\x03x"), None);
+        assert_eq!(decode_real(&[0x40]), Some(f64::INFINITY));
+        assert_eq!(decode_real(&[0x41]), Some(f64::NEG_INFINITY));
+        assert!(decode_real(&[0x42]).unwrap().is_nan());
+        assert!(decode_real(&[0x43]).unwrap().is_sign_negative());
+        assert_eq!(decode_real(&[0x44]), None);
+        assert_eq!(format_real(f64::INFINITY), "PLUS-INFINITY");
+        assert_eq!(format_real(f64::NEG_INFINITY), "MINUS-INFINITY");
+        assert_eq!(format_real(f64::NAN), "NOT-A-NUMBER");
+        assert_eq!(format_real(0.15625), "0.15625");
+    }
+
+    #[test]
+    fn test_decode_restricted_strings() {
+        assert_eq!(
+            decode_restricted_string(18, b"12345").as_deref(),
+            Some("12345")
+        );
+        assert_eq!(decode_restricted_string(26, &[0xE9]), None);
+        // UniversalString "Aé€" as UTF-32BE.
+        let utf32 = [0, 0, 0, 0x41, 0, 0, 0, 0xE9, 0, 0, 0x20, 0xAC];
+        assert_eq!(decode_restricted_string(28, &utf32).as_deref(), Some("Aé€"));
+        assert_eq!(decode_restricted_string(28, &utf32[..5]), None);
+        assert_eq!(decode_restricted_string(28, &[0, 0x11, 0, 0]), None);
+        // Not UTF-8, so GraphicString falls back to Latin-1.
+        assert_eq!(
+            decode_restricted_string(25, &[0x63, 0x61, 0x66, 0xE9]).as_deref(),
+            Some("café")
+        );
+        assert_eq!(decode_restricted_string(4, b"x"), None);
+    }
+}
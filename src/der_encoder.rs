@@ -0,0 +1,384 @@
+// src/der_encoder.rs
+
+use crate::der_parser::{OwnedObject, OwnedValue, Tag, TagClass};
+
+/// Serializes an object tree back to DER.
+/// Lengths are recomputed from the encoded contents rather than taken from
+/// `obj.length`, so trees that were edited after parsing stay well-formed.
+pub fn to_der(obj: &OwnedObject) -> Vec<u8> {
+    let (tag, length, value) = tlv_bytes(obj);
+    [tag, length, value].concat()
+}
+
+/// Encodes `obj` as its separate tag, length and value octets, the pieces
+/// [`to_der`] joins; the hex modal colors each one on its own.
+pub fn tlv_bytes(obj: &OwnedObject) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let value = encode_content(obj);
+    (encode_tag(&obj.tag), encode_length(value.len()), value)
+}
+
+/// Serializes a sequence of top-level objects back to back, e.g. the whole
+/// parsed buffer for export.
+pub fn to_der_all(objects: &[OwnedObject]) -> Vec<u8> {
+    objects.iter().flat_map(to_der).collect()
+}
+
+/// Wraps DER bytes in PEM armor: base64 in 64-column lines between
+/// `-----BEGIN {label}-----` and `-----END {label}-----`.
+pub fn to_pem(der: &[u8], label: &str) -> String {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    // Base64 output is ASCII, so splitting on byte boundaries is safe.
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).unwrap());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
+
+/// Returns just the content octets of `obj`: the raw bytes of a primitive,
+/// or the concatenated encodings of a constructed value's children. DER
+/// requires SET components in ascending order of their encodings, so a SET's
+/// children are sorted; every other type keeps its order.
+pub fn encode_content(obj: &OwnedObject) -> Vec<u8> {
+    match &obj.value {
+        OwnedValue::Primitive(bytes) => bytes.clone(),
+        OwnedValue::Constructed(children) => {
+            let mut encodings: Vec<Vec<u8>> = children.iter().map(to_der).collect();
+            if obj.tag.class == TagClass::Universal && obj.tag.number == 17 {
+                encodings.sort();
+            }
+            encodings.concat()
+        }
+    }
+}
+
+pub fn encode_tag(tag: &Tag) -> Vec<u8> {
+    let class_bits: u8 = match tag.class {
+        TagClass::Universal => 0b00,
+        TagClass::Application => 0b01,
+        TagClass::ContextSpecific => 0b10,
+        TagClass::Private => 0b11,
+    };
+    let mut first_byte = class_bits << 6;
+    if tag.constructed {
+        first_byte |= 0b0010_0000;
+    }
+    if tag.number < 31 {
+        return vec![first_byte | tag.number as u8];
+    }
+    // High-tag-number form: base-128 groups, most significant first,
+    // continuation bit set on every group except the last.
+    let mut groups = vec![];
+    let mut n = tag.number;
+    while n > 0 {
+        groups.push((n & 0x7F) as u8);
+        n >>= 7;
+    }
+    let mut out = vec![first_byte | 0b0001_1111];
+    for (i, group) in groups.iter().rev().enumerate() {
+        if i == groups.len() - 1 {
+            out.push(*group);
+        } else {
+            out.push(group | 0x80);
+        }
+    }
+    out
+}
+
+/// Encodes a definite length in the minimal DER form.
+pub fn encode_length(length: usize) -> Vec<u8> {
+    if length < 128 {
+        return vec![length as u8];
+    }
+    let bytes: Vec<u8> = length
+        .to_be_bytes()
+        .into_iter()
+        .skip_while(|&b| b == 0)
+        .collect();
+    let mut out = vec![0x80 | bytes.len() as u8];
+    out.extend(bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::der_parser::DerParser;
+    use proptest::prelude::*;
+
+    fn arbitrary_tag(constructed: bool) -> impl Strategy<Value = Tag> {
+        let class = prop_oneof![
+            Just(TagClass::Universal),
+            Just(TagClass::Application),
+            Just(TagClass::ContextSpecific),
+            Just(TagClass::Private),
+        ];
+        // Bias towards the single-byte/multi-byte boundary, but still cover
+        // the full u32 range.
+        let number = prop_oneof![0u32..31, 31u32..231, 0x3F80u32..0x4080, any::<u32>()];
+        (class, number).prop_map(move |(class, number)| Tag {
+            class,
+            constructed,
+            number,
+        })
+    }
+
+    fn arbitrary_primitive() -> impl Strategy<Value = OwnedObject> {
+        // Occasionally produce values long enough to need long-form lengths.
+        let bytes = prop_oneof![
+            7 => prop::collection::vec(any::<u8>(), 0..16),
+            1 => prop::collection::vec(any::<u8>(), 0..700),
+        ];
+        (arbitrary_tag(false), bytes).prop_map(|(tag, bytes)| OwnedObject {
+            tag,
+            length: bytes.len(),
+            value: OwnedValue::Primitive(bytes),
+            tlv_start: 0,
+            tlv_end: 0,
+            wrapped: None,
+        })
+    }
+
+    /// Trees up to four levels deep, with up to four children per node.
+    fn arbitrary_object() -> impl Strategy<Value = OwnedObject> {
+        arbitrary_primitive().prop_recursive(4, 64, 4, |inner| {
+            (arbitrary_tag(true), prop::collection::vec(inner, 0..5)).prop_map(
+                |(tag, mut children)| {
+                    // The encoder sorts SET children, so generate them
+                    // pre-sorted for the parsed tree to compare equal.
+                    if tag.class == TagClass::Universal && tag.number == 17 {
+                        children.sort_by_key(to_der);
+                    }
+                    OwnedObject {
+                        tag,
+                        length: children.iter().map(|c| to_der(c).len()).sum(),
+                        value: OwnedValue::Constructed(children),
+                        tlv_start: 0,
+                        tlv_end: 0,
+                        wrapped: None,
+                    }
+                },
+            )
+        })
+    }
+
+    /// Fills in the offsets the parser is expected to report when `obj` is
+    /// encoded starting at `start`, returning the end offset.
+    fn assign_offsets(obj: &mut OwnedObject, start: usize) -> usize {
+        let end = start + to_der(obj).len();
+        let content_start = end - encode_content(obj).len();
+        if let OwnedValue::Constructed(children) = &mut obj.value {
+            let mut child_start = content_start;
+            for child in children {
+                child_start = assign_offsets(child, child_start);
+            }
+        }
+        obj.tlv_start = start;
+        obj.tlv_end = end;
+        end
+    }
+
+    /// Encodes `tag`, checks the encoded length, and parses it back.
+    fn check_tag_round_trip(tag: &Tag) {
+        let encoded = encode_tag(tag);
+        let expected_len = match tag.number {
+            0..=30 => 1,
+            n => 1 + (32 - n.leading_zeros()).div_ceil(7) as usize,
+        };
+        assert_eq!(encoded.len(), expected_len, "{:?}", tag);
+        let mut parser = DerParser::new(&encoded);
+        assert_eq!(parser.read_tag().as_ref(), Some(tag), "{:02X?}", encoded);
+        assert!(parser.is_done(), "{:02X?}", encoded);
+    }
+
+    #[test]
+    fn test_tag_round_trip() {
+        let classes = [
+            TagClass::Universal,
+            TagClass::Application,
+            TagClass::ContextSpecific,
+            TagClass::Private,
+        ];
+        // Every base-128 group boundary, including the 30/31 switch to the
+        // high-tag-number form; `test_arbitrary_tag_round_trip` samples the
+        // rest of the range.
+        let boundaries = (0..7)
+            .flat_map(|groups| [(1u64 << (7 * groups)) - 1, 1 << (7 * groups)])
+            .chain([30, 31, 32, u32::MAX as u64])
+            .filter_map(|n| u32::try_from(n).ok());
+        for number in (0..=300).chain(boundaries) {
+            for class in &classes {
+                for constructed in [false, true] {
+                    check_tag_round_trip(&Tag {
+                        class: class.clone(),
+                        constructed,
+                        number,
+                    });
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_arbitrary_tag_round_trip(
+            tag in prop_oneof![arbitrary_tag(false), arbitrary_tag(true)],
+        ) {
+            check_tag_round_trip(&tag);
+        }
+
+        #[test]
+        fn test_round_trip_arbitrary_trees(mut obj in arbitrary_object()) {
+            assign_offsets(&mut obj, 0);
+            let der = to_der(&obj);
+            let parsed = DerParser::new(&der).parse_all();
+            prop_assert!(parsed.is_ok(), "failed to re-parse {:02X?}: {:?}", der, parsed);
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(parsed.len(), 1);
+            prop_assert_eq!(OwnedObject::from(&parsed[0]), obj);
+        }
+    }
+
+    fn round_trip_pem(pem: &str) {
+        let der = crate::der_parser::try_decode_input(pem).unwrap();
+        let objects = crate::der_parser::parse_owned(&der).unwrap();
+        assert_eq!(to_der_all(&objects), der);
+    }
+
+    #[test]
+    fn test_round_trip_certificate() {
+        round_trip_pem(include_str!("../testCert.pem"));
+    }
+
+    #[test]
+    fn test_round_trip_csr_and_keys() {
+        round_trip_pem(include_str!("../testCSR.pem"));
+        round_trip_pem(include_str!("../testKey.pem"));
+        round_trip_pem(include_str!("../testKey2.pem"));
+    }
+
+    #[test]
+    fn test_to_der_all_concatenates() {
+        let der = [0x02, 0x01, 0x01, 0x30, 0x03, 0x02, 0x01, 0x02];
+        let objects = crate::der_parser::parse_owned(&der).unwrap();
+        assert_eq!(to_der_all(&objects), der);
+    }
+
+    fn integer(value: u8) -> OwnedObject {
+        OwnedObject {
+            tag: Tag {
+                class: TagClass::Universal,
+                constructed: false,
+                number: 2,
+            },
+            length: 1,
+            value: OwnedValue::Primitive(vec![value]),
+            tlv_start: 0,
+            tlv_end: 0,
+            wrapped: None,
+        }
+    }
+
+    fn constructed(number: u32, children: Vec<OwnedObject>) -> OwnedObject {
+        OwnedObject {
+            tag: Tag {
+                class: TagClass::Universal,
+                constructed: true,
+                number,
+            },
+            length: 0,
+            value: OwnedValue::Constructed(children),
+            tlv_start: 0,
+            tlv_end: 0,
+            wrapped: None,
+        }
+    }
+
+    #[test]
+    fn test_set_children_are_sorted() {
+        let set = constructed(17, vec![integer(3), integer(1), integer(2)]);
+        assert_eq!(
+            to_der(&set),
+            vec![
+                0x31, 0x09, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x01, 0x03
+            ]
+        );
+        // Sorting is by the whole encoding, tag byte first.
+        let set = constructed(17, vec![constructed(16, vec![]), integer(9)]);
+        assert_eq!(to_der(&set), vec![0x31, 0x05, 0x02, 0x01, 0x09, 0x30, 0x00]);
+    }
+
+    #[test]
+    fn test_sequence_order_is_preserved() {
+        let seq = constructed(16, vec![integer(3), integer(1)]);
+        assert_eq!(
+            to_der(&seq),
+            vec![0x30, 0x06, 0x02, 0x01, 0x03, 0x02, 0x01, 0x01]
+        );
+    }
+
+    #[test]
+    fn test_to_pem_wraps_at_64_columns() {
+        // 60 bytes encode to 80 base64 characters: one full line and 16 more.
+        let der: Vec<u8> = (0u8..60).collect();
+        let pem = to_pem(&der, "CERTIFICATE");
+        let lines: Vec<&str> = pem.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "-----BEGIN CERTIFICATE-----",
+                "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v",
+                "MDEyMzQ1Njc4OTo7",
+                "-----END CERTIFICATE-----",
+            ]
+        );
+        assert!(pem.ends_with('\n'));
+        assert_eq!(
+            to_pem(&[0x05, 0x00], "X"),
+            "-----BEGIN X-----\nBQA=\n-----END X-----\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_length_boundaries() {
+        assert_eq!(encode_length(0), vec![0x00]);
+        assert_eq!(encode_length(127), vec![0x7F]);
+        assert_eq!(encode_length(128), vec![0x81, 0x80]);
+        assert_eq!(encode_length(256), vec![0x82, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_tlv_bytes() {
+        let parse = |hex: &str| crate::der_parser::parse_owned(&hex::decode(hex).unwrap()).unwrap();
+
+        // INTEGER 5
+        let integer = parse("020105");
+        assert_eq!(tlv_bytes(&integer[0]), (vec![0x02], vec![0x01], vec![0x05]));
+
+        // SEQUENCE { SEQUENCE { NULL } }: the value holds the children's TLVs.
+        let nested = parse("300430020500");
+        assert_eq!(
+            tlv_bytes(&nested[0]),
+            (vec![0x30], vec![0x04], vec![0x30, 0x02, 0x05, 0x00])
+        );
+
+        // A 200-byte OCTET STRING needs the long length form.
+        let mut long = vec![0x04, 0x81, 0xC8];
+        long.extend([0xAB; 200]);
+        let long = crate::der_parser::parse_owned(&long).unwrap();
+        let (tag, length, value) = tlv_bytes(&long[0]);
+        assert_eq!((tag, length), (vec![0x04], vec![0x81, 0xC8]));
+        assert_eq!(value, vec![0xAB; 200]);
+
+        // [APPLICATION 200] primitive, a high tag number.
+        let high = parse("5F81480101");
+        assert_eq!(
+            tlv_bytes(&high[0]),
+            (vec![0x5F, 0x81, 0x48], vec![0x01], vec![0x01])
+        );
+        assert_eq!(to_der(&high[0]), hex::decode("5F81480101").unwrap());
+    }
+}
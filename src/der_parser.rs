@@ -5,6 +5,64 @@ use base64::Engine;
 pub struct DerParser<'a> {
     input: &'a [u8],
     position: usize,
+    /// Reads stop here: the end of the innermost definite-length value being
+    /// parsed, or the end of the input at the top level.
+    end: usize,
+    config: ParserConfig,
+    /// Constructed values whose contents are still being parsed, innermost
+    /// last. Kept between calls so its capacity is reused.
+    stack: Vec<OpenConstructed>,
+    /// Finished children of every open constructed value, in order. Each
+    /// value's children are moved into an exactly-sized `Vec` when it closes.
+    pending: Vec<ASN1Object<'a>>,
+}
+
+/// A constructed value on the `DerParser` work stack.
+struct OpenConstructed {
+    tag: Tag,
+    tlv_start: usize,
+    content_start: usize,
+    /// `None` for an indefinite length, which ends at an end-of-contents marker.
+    content_end: Option<usize>,
+    /// The read limit to restore once this value is finished.
+    outer_end: usize,
+    /// Index of this value's first child in the pending children.
+    first_child: usize,
+}
+
+/// Limits and options that control how permissive the parser is.
+/// The defaults are strict DER with bounds suitable for untrusted input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserConfig {
+    /// Maximum nesting depth of constructed values.
+    pub max_depth: usize,
+    /// Maximum content length accepted for any single TLV.
+    pub max_length: usize,
+    /// Which encoding rules the input is expected to follow.
+    pub mode: EncodingRules,
+    /// Reject lengths that are not in their shortest form (long form for a
+    /// length below 128, or leading zero length octets), as DER requires.
+    /// Off by default so broken encodings can still be inspected.
+    pub strict_lengths: bool,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_length: 64 * 1024 * 1024,
+            mode: EncodingRules::Der,
+            strict_lengths: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncodingRules {
+    Der,
+    /// BER additionally allows indefinite-length constructed values,
+    /// terminated by an end-of-contents `00 00` marker.
+    Ber,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -22,11 +80,47 @@ pub struct Tag {
     pub number: u32,
 }
 
+impl Tag {
+    /// Returns a warning when the constructed bit contradicts the form X.690
+    /// mandates for this universal type, e.g. a SEQUENCE encoded as primitive.
+    pub fn form_warning(&self) -> Option<String> {
+        if self.class != TagClass::Universal {
+            return None;
+        }
+        let (name, must_be_constructed) = match self.number {
+            1 => ("BOOLEAN", false),
+            2 => ("INTEGER", false),
+            5 => ("NULL", false),
+            6 => ("OBJECT IDENTIFIER", false),
+            9 => ("REAL", false),
+            10 => ("ENUMERATED", false),
+            13 => ("RELATIVE-OID", false),
+            16 => ("SEQUENCE", true),
+            17 => ("SET", true),
+            _ => return None,
+        };
+        match (must_be_constructed, self.constructed) {
+            (true, false) => Some(format!("{} encoded as primitive", name)),
+            (false, true) => Some(format!("{} encoded as constructed", name)),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ASN1Object<'a> {
     pub tag: Tag,
+    /// Number of content octets, as read by [`DerParser::read_length`]; the
+    /// tag and length octets are not included. For a BER indefinite length
+    /// it is the contents up to, but not including, the end-of-contents
+    /// marker.
     pub length: usize,
     pub value: ASN1Value<'a>,
+    /// Offset of the first tag byte in the source buffer.
+    pub tlv_start: usize,
+    /// Offset one past the last byte of the value (including any
+    /// end-of-contents marker) in the source buffer.
+    pub tlv_end: usize,
 }
 
 #[derive(Debug, PartialEq)]
@@ -35,24 +129,90 @@ pub enum ASN1Value<'a> {
     Constructed(Vec<ASN1Object<'a>>),
 }
 
+/// A parse failure. Every variant carries the absolute byte offset in the
+/// original buffer that it refers to: the tag for tag and depth errors, the
+/// first length octet for length errors, and the first unexpected byte (or
+/// the end of input) otherwise.
 #[derive(Debug, PartialEq)]
 pub enum ASN1Error {
-    UnexpectedEOF,
-    InvalidTag,
-    InvalidLength,
-    IndefiniteLengthNotAllowed,
-    TrailingData,
+    UnexpectedEOF { offset: usize },
+    InvalidTag { offset: usize },
+    InvalidLength { offset: usize },
+    IndefiniteLengthNotAllowed { offset: usize },
+    TrailingData { offset: usize },
+    MaxDepthExceeded { offset: usize },
+    LengthLimitExceeded { offset: usize },
+}
+
+impl ASN1Error {
+    pub fn offset(&self) -> usize {
+        match self {
+            ASN1Error::UnexpectedEOF { offset }
+            | ASN1Error::InvalidTag { offset }
+            | ASN1Error::InvalidLength { offset }
+            | ASN1Error::IndefiniteLengthNotAllowed { offset }
+            | ASN1Error::TrailingData { offset }
+            | ASN1Error::MaxDepthExceeded { offset }
+            | ASN1Error::LengthLimitExceeded { offset } => *offset,
+        }
+    }
+}
+
+impl std::fmt::Display for ASN1Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = match self {
+            ASN1Error::UnexpectedEOF { .. } => "unexpected end of input",
+            ASN1Error::InvalidTag { .. } => "invalid tag",
+            ASN1Error::InvalidLength { .. } => "invalid length",
+            ASN1Error::IndefiniteLengthNotAllowed { .. } => "indefinite length not allowed",
+            ASN1Error::TrailingData { .. } => "trailing data",
+            ASN1Error::MaxDepthExceeded { .. } => "maximum nesting depth exceeded",
+            ASN1Error::LengthLimitExceeded { .. } => "length exceeds the configured limit",
+        };
+        write!(f, "{} at offset {}", what, self.offset())
+    }
 }
 
+#[derive(Debug, PartialEq, Clone)]
 pub enum OwnedValue {
     Primitive(Vec<u8>),
     Constructed(Vec<OwnedObject>),
 }
 
+#[derive(Debug, PartialEq, Clone)]
 pub struct OwnedObject {
     pub tag: Tag,
+    /// Number of content octets, as in [`ASN1Object::length`]. Edits keep it
+    /// equal to the length of the re-encoded contents.
     pub length: usize,
     pub value: OwnedValue,
+    /// Source offsets as in [`ASN1Object`], or both 0 for nodes that were
+    /// edited or added and so have no bytes in the source buffer.
+    pub tlv_start: usize,
+    pub tlv_end: usize,
+    /// The objects a primitive OCTET STRING or BIT STRING wraps, once `E` has
+    /// parsed them, shown as its children. The node still encodes from its
+    /// primitive `value`; edits below it are written back into those bytes.
+    pub wrapped: Option<Vec<OwnedObject>>,
+}
+
+impl OwnedObject {
+    /// The nodes below this one in the tree: a constructed value's children,
+    /// or the objects a primitive wraps.
+    pub fn children(&self) -> Option<&Vec<OwnedObject>> {
+        match &self.value {
+            OwnedValue::Constructed(children) => Some(children),
+            OwnedValue::Primitive(_) => self.wrapped.as_ref(),
+        }
+    }
+
+    /// Mutable counterpart of [`OwnedObject::children`].
+    pub fn children_mut(&mut self) -> Option<&mut Vec<OwnedObject>> {
+        match &mut self.value {
+            OwnedValue::Constructed(children) => Some(children),
+            OwnedValue::Primitive(_) => self.wrapped.as_mut(),
+        }
+    }
 }
 
 impl<'a> From<&ASN1Object<'a>> for OwnedObject {
@@ -69,21 +229,35 @@ impl<'a> From<&ASN1Object<'a>> for OwnedObject {
             tag: src.tag.clone(),
             length,
             value,
+            tlv_start: src.tlv_start,
+            tlv_end: src.tlv_end,
+            wrapped: None,
         }
     }
 }
 
 impl<'a> DerParser<'a> {
     pub fn new(input: &'a [u8]) -> Self {
-        Self { input, position: 0 }
+        Self::with_config(input, ParserConfig::default())
+    }
+
+    pub fn with_config(input: &'a [u8], config: ParserConfig) -> Self {
+        Self {
+            input,
+            position: 0,
+            end: input.len(),
+            config,
+            stack: Vec::new(),
+            pending: Vec::new(),
+        }
     }
 
     pub fn peek(&self) -> Option<u8> {
-        self.input.get(self.position).copied()
+        self.input[..self.end].get(self.position).copied()
     }
 
     pub fn read_byte(&mut self) -> Option<u8> {
-        if self.position < self.input.len() {
+        if self.position < self.end {
             let byte = self.input[self.position];
             self.position += 1;
             Some(byte)
@@ -93,7 +267,7 @@ impl<'a> DerParser<'a> {
     }
 
     pub fn read_n(&mut self, n: usize) -> Option<&'a [u8]> {
-        if self.position + n <= self.input.len() {
+        if n <= self.remaining() {
             let slice = &self.input[self.position..self.position + n];
             self.position += n;
             Some(slice)
@@ -102,8 +276,13 @@ impl<'a> DerParser<'a> {
         }
     }
 
+    /// Number of unread bytes left in the input.
+    pub fn remaining(&self) -> usize {
+        self.end.saturating_sub(self.position)
+    }
+
     pub fn is_done(&self) -> bool {
-        self.position >= self.input.len()
+        self.position >= self.end
     }
 
     pub fn read_tag(&mut self) -> Option<Tag> {
@@ -124,6 +303,11 @@ impl<'a> DerParser<'a> {
             number = 0;
             loop {
                 let byte = self.read_byte()? as u32;
+                // Tag numbers that don't fit in a u32 are rejected rather
+                // than silently truncated.
+                if number >> 25 != 0 {
+                    return None;
+                }
                 number = (number << 7) | (byte & 0b0111_1111);
                 if (byte & 0b1000_0000) == 0 {
                     break;
@@ -152,10 +336,18 @@ impl<'a> DerParser<'a> {
             }
 
             let bytes = self.read_n(num_bytes)?;
+            if self.config.strict_lengths && bytes[0] == 0 {
+                return None;
+            }
             let mut length = 0usize;
 
+            // Checked so an oversized length can't wrap around to a small
+            // value, notably on 32-bit targets.
             for &b in bytes {
-                length = (length << 8) | b as usize;
+                length = length.checked_mul(256)?.checked_add(b as usize)?;
+            }
+            if self.config.strict_lengths && length < 0x80 {
+                return None;
             }
             Some(length)
         }
@@ -165,59 +357,678 @@ impl<'a> DerParser<'a> {
         self.read_n(length)
     }
 
+    /// Parses one TLV, including everything nested inside it. Constructed
+    /// values are parsed in place with an explicit stack rather than by
+    /// recursion, so deep inputs are bounded by `max_depth`, not the call stack.
     pub fn parse_tlv(&mut self) -> Result<ASN1Object<'a>, ASN1Error> {
-        let tag = self.read_tag().ok_or(ASN1Error::InvalidTag)?;
-        let length = self.read_length().ok_or(ASN1Error::InvalidLength)?;
-        let value = self.read_value(length).ok_or(ASN1Error::UnexpectedEOF)?;
-        let value = if tag.constructed {
-            // println!("Constructed tag value length = {}, bytes = {:02X?}", value.len(), value);
-            let mut parser = DerParser::new(value);
-            let result = parser.parse_all()?;
-            ASN1Value::Constructed(result)
-        } else {
-            ASN1Value::Primitive(value)
-        };
-        Ok(ASN1Object { tag, length, value })
+        let mut pending = std::mem::take(&mut self.pending);
+        let result = self.parse_node(&mut pending);
+        self.pending = pending;
+        result
+    }
+
+    /// Parses one TLV into nodes of type `N`, using `pending` to hold the
+    /// finished children of open constructed values.
+    fn parse_node<N: ParsedNode<'a>>(&mut self, pending: &mut Vec<N>) -> Result<N, ASN1Error> {
+        let outer_end = self.end;
+        let result = self.parse_tlv_iterative(pending);
+        if result.is_err() {
+            self.end = outer_end;
+            self.stack.clear();
+            pending.clear();
+        }
+        result
+    }
+
+    fn parse_tlv_iterative<N: ParsedNode<'a>>(
+        &mut self,
+        pending: &mut Vec<N>,
+    ) -> Result<N, ASN1Error> {
+        loop {
+            // Close every open value whose contents are complete, attaching it
+            // to its parent; a finished outermost value is the result.
+            while let Some(open) = self.stack.last() {
+                let length = match open.content_end {
+                    Some(end) if self.position == end => end - open.content_start,
+                    Some(_) => break,
+                    None if self.input[self.position..self.end].starts_with(&[0x00, 0x00]) => {
+                        let length = self.position - open.content_start;
+                        self.position += 2;
+                        length
+                    }
+                    None if self.is_done() => {
+                        return Err(ASN1Error::UnexpectedEOF {
+                            offset: self.position,
+                        });
+                    }
+                    None => break,
+                };
+                let open = self.stack.pop().expect("stack is not empty");
+                self.end = open.outer_end;
+                let object = N::constructed(
+                    open.tag,
+                    length,
+                    pending.drain(open.first_child..).collect(),
+                    open.tlv_start..self.position,
+                );
+                if self.stack.is_empty() {
+                    return Ok(object);
+                }
+                pending.push(object);
+            }
+
+            let tlv_start = self.position;
+            let tag = self
+                .read_tag()
+                .ok_or(ASN1Error::InvalidTag { offset: tlv_start })?;
+            let offset = self.position;
+            let content_end = if self.peek() == Some(0x80) {
+                if self.config.mode != EncodingRules::Ber || !tag.constructed {
+                    return Err(ASN1Error::IndefiniteLengthNotAllowed { offset });
+                }
+                self.read_byte();
+                None
+            } else {
+                let length = self
+                    .read_length()
+                    .ok_or(ASN1Error::InvalidLength { offset })?;
+                if length > self.config.max_length {
+                    return Err(ASN1Error::LengthLimitExceeded { offset });
+                }
+                if length > self.remaining() {
+                    return Err(ASN1Error::UnexpectedEOF { offset });
+                }
+                Some(self.position + length)
+            };
+
+            if tag.constructed {
+                if self.stack.len() >= self.config.max_depth {
+                    return Err(ASN1Error::MaxDepthExceeded { offset: tlv_start });
+                }
+                self.stack.push(OpenConstructed {
+                    tag,
+                    tlv_start,
+                    content_start: self.position,
+                    content_end,
+                    outer_end: self.end,
+                    first_child: pending.len(),
+                });
+                if let Some(end) = content_end {
+                    self.end = end;
+                }
+                continue;
+            }
+
+            let length =
+                content_end.expect("primitive values have a definite length") - self.position;
+            let value = self
+                .read_value(length)
+                .ok_or(ASN1Error::UnexpectedEOF { offset })?;
+            let object = N::primitive(tag, value, tlv_start..self.position);
+            if self.stack.is_empty() {
+                return Ok(object);
+            }
+            pending.push(object);
+        }
+    }
+
+    /// Parses exactly one TLV, for inputs such as a certificate where bytes
+    /// left over after the first object indicate corruption.
+    pub fn parse_single(&mut self) -> Result<ASN1Object<'a>, ASN1Error> {
+        let object = self.parse_tlv()?;
+        if !self.is_done() {
+            return Err(ASN1Error::TrailingData {
+                offset: self.position,
+            });
+        }
+        Ok(object)
     }
 
     pub fn parse_all(&mut self) -> Result<Vec<ASN1Object<'a>>, ASN1Error> {
-        let mut der_data = Vec::new();
-        while !self.is_done() {
-            // println!("Parsing new TLV at position {}", self.position);
-            let object = self.parse_tlv();
-            match object {
-                Ok(object) => der_data.push(object),
-                Err(err) => return Err(err),
+        self.collect()
+    }
+
+    /// Like [`DerParser::parse_all`], but keeps the top-level objects parsed
+    /// before a malformed or truncated TLV instead of discarding them.
+    pub fn parse_all_lenient(&mut self) -> LenientParse<ASN1Object<'a>> {
+        let mut pending = std::mem::take(&mut self.pending);
+        let parsed = self.parse_lenient(&mut pending);
+        self.pending = pending;
+        parsed
+    }
+
+    /// Like [`DerParser::parse_all_lenient`], but builds owned trees
+    /// directly, copying each primitive value once.
+    pub fn parse_all_lenient_owned(&mut self) -> LenientParse<OwnedObject> {
+        self.parse_lenient(&mut Vec::new())
+    }
+
+    fn parse_lenient<N: ParsedNode<'a>>(&mut self, pending: &mut Vec<N>) -> LenientParse<N> {
+        let mut objects = Vec::new();
+        while let Some(result) = self.next_node(pending) {
+            match result {
+                Ok(object) => objects.push(object),
+                Err(error) => {
+                    return LenientParse {
+                        objects,
+                        error: Some(error),
+                    };
+                }
+            }
+        }
+        LenientParse {
+            objects,
+            error: None,
+        }
+    }
+
+    /// The next top-level object, or `None` at the end of the input.
+    fn next_node<N: ParsedNode<'a>>(
+        &mut self,
+        pending: &mut Vec<N>,
+    ) -> Option<Result<N, ASN1Error>> {
+        if self.is_done() {
+            return None;
+        }
+        let result = self.parse_node(pending);
+        if result.is_err() {
+            // Nothing after a malformed TLV can be located reliably.
+            self.position = self.end;
+        }
+        Some(result)
+    }
+}
+
+/// A tree node the parser can build: the borrowed [`ASN1Object`] or the
+/// [`OwnedObject`], so owned trees are built directly rather than by
+/// converting a borrowed tree afterwards.
+trait ParsedNode<'a>: Sized {
+    fn primitive(tag: Tag, value: &'a [u8], tlv: std::ops::Range<usize>) -> Self;
+    fn constructed(
+        tag: Tag,
+        length: usize,
+        children: Vec<Self>,
+        tlv: std::ops::Range<usize>,
+    ) -> Self;
+}
+
+impl<'a> ParsedNode<'a> for ASN1Object<'a> {
+    fn primitive(tag: Tag, value: &'a [u8], tlv: std::ops::Range<usize>) -> Self {
+        ASN1Object {
+            tag,
+            length: value.len(),
+            value: ASN1Value::Primitive(value),
+            tlv_start: tlv.start,
+            tlv_end: tlv.end,
+        }
+    }
+
+    fn constructed(
+        tag: Tag,
+        length: usize,
+        children: Vec<Self>,
+        tlv: std::ops::Range<usize>,
+    ) -> Self {
+        ASN1Object {
+            tag,
+            length,
+            value: ASN1Value::Constructed(children),
+            tlv_start: tlv.start,
+            tlv_end: tlv.end,
+        }
+    }
+}
+
+impl<'a> ParsedNode<'a> for OwnedObject {
+    fn primitive(tag: Tag, value: &'a [u8], tlv: std::ops::Range<usize>) -> Self {
+        OwnedObject {
+            tag,
+            length: value.len(),
+            value: OwnedValue::Primitive(value.to_vec()),
+            tlv_start: tlv.start,
+            tlv_end: tlv.end,
+            wrapped: None,
+        }
+    }
+
+    fn constructed(
+        tag: Tag,
+        length: usize,
+        children: Vec<Self>,
+        tlv: std::ops::Range<usize>,
+    ) -> Self {
+        OwnedObject {
+            tag,
+            length,
+            value: OwnedValue::Constructed(children),
+            tlv_start: tlv.start,
+            tlv_end: tlv.end,
+            wrapped: None,
+        }
+    }
+}
+
+/// The result of [`DerParser::parse_all_lenient`] and
+/// [`DerParser::parse_all_lenient_owned`].
+#[derive(Debug, PartialEq)]
+pub struct LenientParse<T> {
+    /// Every top-level object parsed before the first error.
+    pub objects: Vec<T>,
+    /// Why parsing stopped early; `None` when the whole input was parsed.
+    pub error: Option<ASN1Error>,
+}
+
+/// Yields the top-level objects one at a time, so callers can stop early
+/// without parsing the rest of the input. Iteration ends at the end of the
+/// input or after the first error.
+///
+/// ```
+/// use asn1smith::der_parser::DerParser;
+///
+/// // INTEGER 1, SEQUENCE {}, then bytes that are never looked at.
+/// let input = [0x02, 0x01, 0x01, 0x30, 0x00, 0xFF, 0xFF];
+/// let first_sequence = DerParser::new(&input)
+///     .find_map(|object| object.ok().filter(|o| o.tag.number == 16));
+/// assert_eq!(first_sequence.unwrap().tlv_start, 3);
+/// ```
+impl<'a> Iterator for DerParser<'a> {
+    type Item = Result<ASN1Object<'a>, ASN1Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut pending = std::mem::take(&mut self.pending);
+        let result = self.next_node(&mut pending);
+        self.pending = pending;
+        result
+    }
+}
+
+/// Parses every top-level object in `input` into owned trees, for callers
+/// that don't need the zero-copy `DerParser` API and its borrowed lifetimes.
+///
+/// ```
+/// use asn1smith::der_parser::{OwnedValue, parse_owned};
+///
+/// // SEQUENCE { INTEGER 5 }
+/// let objects = parse_owned(&[0x30, 0x03, 0x02, 0x01, 0x05]).unwrap();
+/// assert_eq!(objects[0].tag.number, 16);
+/// let OwnedValue::Constructed(children) = &objects[0].value else {
+///     panic!("expected a SEQUENCE");
+/// };
+/// assert_eq!(children[0].value, OwnedValue::Primitive(vec![5]));
+/// ```
+pub fn parse_owned(input: &[u8]) -> Result<Vec<OwnedObject>, ASN1Error> {
+    let parsed = DerParser::new(input).parse_all_lenient_owned();
+    match parsed.error {
+        Some(error) => Err(error),
+        None => Ok(parsed.objects),
+    }
+}
+
+/// Like [`parse_owned`], but pairs each top-level object with the byte range
+/// of `input` it was parsed from, for tools that map parse output back onto
+/// the raw bytes. Nested objects carry their own `tlv_start`/`tlv_end`.
+///
+/// ```
+/// use asn1smith::der_parser::parse_with_spans;
+///
+/// // INTEGER 5, NULL
+/// let spans = parse_with_spans(&[0x02, 0x01, 0x05, 0x05, 0x00]).unwrap();
+/// assert_eq!(spans[0].1, 0..3);
+/// assert_eq!(spans[1].1, 3..5);
+/// ```
+pub fn parse_with_spans(
+    input: &[u8],
+) -> Result<Vec<(OwnedObject, std::ops::Range<usize>)>, ASN1Error> {
+    Ok(parse_owned(input)?
+        .into_iter()
+        .map(|object| {
+            let span = object.tlv_start..object.tlv_end;
+            (object, span)
+        })
+        .collect())
+}
+
+/// Looks up a node in a parsed tree: `path[0]` indexes the top-level
+/// `objects` and each later index picks a child of the node before it.
+/// Returns `None` for an empty path, an index out of range, or a path that
+/// continues below a node with no [`OwnedObject::children`].
+pub fn get_by_path<'a>(objects: &'a [OwnedObject], path: &[usize]) -> Option<&'a OwnedObject> {
+    let (&first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(objects.get(first)?, |obj, &idx| obj.children()?.get(idx))
+}
+
+/// Mutable counterpart of [`get_by_path`].
+pub fn get_by_path_mut<'a>(
+    objects: &'a mut [OwnedObject],
+    path: &[usize],
+) -> Option<&'a mut OwnedObject> {
+    let (&first, rest) = path.split_first()?;
+    rest.iter().try_fold(objects.get_mut(first)?, |obj, &idx| {
+        obj.children_mut()?.get_mut(idx)
+    })
+}
+
+/// Splits a single encoded TLV into its tag, length, and value bytes.
+/// An indefinite length yields a one-byte length field and leaves the
+/// contents plus end-of-contents marker in the value.
+pub fn split_tlv(bytes: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let mut parser = DerParser::new(bytes);
+    parser.read_tag()?;
+    let tag_end = parser.position;
+    if parser.peek() == Some(0x80) {
+        parser.read_byte();
+    } else {
+        parser.read_length()?;
+    }
+    let length_end = parser.position;
+    Some((
+        &bytes[..tag_end],
+        &bytes[tag_end..length_end],
+        &bytes[length_end..],
+    ))
+}
+
+/// Interprets the contents of an input file: text files go through
+/// `try_decode_input` (PEM, base64, or hex), anything else is taken as raw DER.
+pub fn decode_file_bytes(bytes: Vec<u8>) -> Vec<u8> {
+    match std::str::from_utf8(&bytes).map(try_decode_input) {
+        Ok(Ok(decoded)) => decoded,
+        _ => bytes,
+    }
+}
+
+/// Why `try_decode_input` could not turn the input into bytes.
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    /// The input is blank.
+    Empty,
+    /// The input looks like hex but breaks at `position` (a byte offset into
+    /// the original input): either a character that is neither hex nor
+    /// base64, or an unpaired final digit.
+    NotHex { position: usize },
+    /// Contains non-hex characters and is not valid base64 either.
+    NotBase64,
+    /// A PEM block's body has a character at `position` (a byte offset into
+    /// the original input) that is not base64.
+    InvalidBase64 { position: usize },
+    /// Nothing was left once PEM boundary lines and whitespace were removed.
+    EmptyAfterStripping,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::Empty => write!(f, "input is empty"),
+            DecodeError::NotHex { position } => write!(f, "not valid hex at offset {}", position),
+            DecodeError::NotBase64 => write!(f, "input is neither hex nor valid base64"),
+            DecodeError::InvalidBase64 { position } => {
+                write!(f, "not valid base64 at offset {}", position)
+            }
+            DecodeError::EmptyAfterStripping => {
+                write!(f, "no data between the PEM boundary lines")
             }
         }
-        Ok(der_data)
     }
 }
 
-pub fn try_decode_input(input: &str) -> Result<Vec<u8>, ()> {
-    let cleaned: String = input
-        .lines()
-        .filter(|line| !line.starts_with("-----")) // Strip PEM boundaries
-        .collect::<Vec<_>>()
-        .join("");
+/// One `-----BEGIN {label}-----` ... `-----END {label}-----` block of PEM input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PemBlock {
+    /// The type named in the BEGIN line, e.g. `CERTIFICATE`.
+    pub label: String,
+    pub der: Vec<u8>,
+}
+
+/// Finds every PEM block in `input` and decodes each body on its own, so the
+/// padding at the end of one block can't corrupt the next. Text outside the
+/// blocks is ignored; a block without an END line runs to the end of the
+/// input. Returns an empty list when there is no BEGIN line at all.
+pub fn decode_pem_blocks(input: &str) -> Result<Vec<PemBlock>, DecodeError> {
+    let mut blocks = Vec::new();
+    // The label and body characters (with their offsets) of the open block.
+    let mut open: Option<(String, Vec<(usize, char)>)> = None;
+    let mut line_start = 0;
+    for line in input.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if let Some(label) = trimmed
+            .strip_prefix("-----BEGIN ")
+            .and_then(|rest| rest.strip_suffix("-----"))
+        {
+            if let Some((label, body)) = open.take() {
+                blocks.push(PemBlock {
+                    label,
+                    der: decode_pem_body(&body)?,
+                });
+            }
+            open = Some((label.to_string(), Vec::new()));
+        } else if trimmed.starts_with("-----END ") {
+            if let Some((label, body)) = open.take() {
+                blocks.push(PemBlock {
+                    label,
+                    der: decode_pem_body(&body)?,
+                });
+            }
+        } else if let Some((_, body)) = &mut open {
+            body.extend(
+                line.char_indices()
+                    .filter(|(_, c)| !c.is_ascii_whitespace())
+                    .map(|(i, c)| (line_start + i, c)),
+            );
+        }
+        line_start += line.len();
+    }
+    if let Some((label, body)) = open {
+        blocks.push(PemBlock {
+            label,
+            der: decode_pem_body(&body)?,
+        });
+    }
+    Ok(blocks)
+}
+
+fn decode_pem_body(body: &[(usize, char)]) -> Result<Vec<u8>, DecodeError> {
+    if body.is_empty() {
+        return Err(DecodeError::EmptyAfterStripping);
+    }
+    if let Some(&(position, _)) = body.iter().find(|&&(_, c)| !is_base64_char(c)) {
+        return Err(DecodeError::InvalidBase64 { position });
+    }
+    decode_base64(body)
+}
+
+fn is_base64_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')
+}
 
-    // Try hex first
-    if let Ok(bytes) = hex::decode(&cleaned) {
-        return Ok(bytes);
+/// Decodes base64 characters collected with their offsets in the input, so
+/// the first invalid one can be reported.
+fn decode_base64(cleaned: &[(usize, char)]) -> Result<Vec<u8>, DecodeError> {
+    if let Some(&(position, _)) = cleaned.iter().find(|&&(_, c)| !is_base64_char(c)) {
+        return Err(DecodeError::NotHex { position });
     }
+    let text: String = cleaned.iter().map(|&(_, c)| c).collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(&text)
+        .map_err(|_| DecodeError::NotBase64)
+}
+
+/// The characters of `line` left once it is read as a list of hex bytes:
+/// whitespace and commas separate tokens, and a token of hex digits may
+/// start with a `0x`/`0X` prefix, as in a C or Rust byte-array literal.
+/// Other tokens are kept whole, so base64 such as `0xQUJDQA` is left intact.
+fn hex_literal_digits(line: &str) -> Vec<(usize, char)> {
+    let mut digits = Vec::new();
+    let mut offset = 0;
+    for token in line.split(|c: char| c.is_ascii_whitespace() || c == ',') {
+        let start = offset;
+        offset += token.len() + 1;
+        let skip = match token.get(..2) {
+            Some("0x" | "0X") if token[2..].bytes().all(|b| b.is_ascii_hexdigit()) => 2,
+            _ => 0,
+        };
+        digits.extend(
+            token[skip..]
+                .char_indices()
+                .map(|(i, c)| (start + skip + i, c)),
+        );
+    }
+    digits
+}
+
+/// Decodes pasted input as hex or base64, ignoring PEM boundary lines and
+/// all whitespace. Input made only of hex digits is always treated as hex;
+/// base64 is tried only when other characters are present. The hex branch
+/// also accepts byte-array literals such as `0x30, 0x82, 0x01`. Each block of
+/// PEM input (e.g. a certificate chain) is decoded separately and the
+/// results are concatenated, so every block becomes its own top-level object.
+pub fn try_decode_input(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_input_blocks(input).map(|blocks| blocks.concat())
+}
 
-    // Try base64 next
-    if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(&cleaned) {
-        return Ok(bytes);
+/// Like [`try_decode_input`], but keeps the bytes of each PEM block apart.
+/// Input without PEM blocks decodes to a single block.
+pub fn decode_input_blocks(input: &str) -> Result<Vec<Vec<u8>>, DecodeError> {
+    if input.trim().is_empty() {
+        return Err(DecodeError::Empty);
     }
+    let blocks = decode_pem_blocks(input)?;
+    if !blocks.is_empty() {
+        return Ok(blocks.into_iter().map(|block| block.der).collect());
+    }
+    decode_hex_or_base64(input).map(|bytes| vec![bytes])
+}
 
-    Err(())
+/// The hex and base64 branches of [`try_decode_input`], for input without
+/// PEM blocks.
+fn decode_hex_or_base64(input: &str) -> Result<Vec<u8>, DecodeError> {
+    // Keep each character's offset in `input` so errors can point at it.
+    let mut cleaned: Vec<(usize, char)> = Vec::new();
+    let mut hex_digits: Vec<(usize, char)> = Vec::new();
+    let mut line_start = 0;
+    for line in input.split_inclusive('\n') {
+        if !line.trim_start().starts_with("-----") {
+            // Strip PEM boundaries
+            cleaned.extend(
+                line.char_indices()
+                    .filter(|(_, c)| !c.is_ascii_whitespace())
+                    .map(|(i, c)| (line_start + i, c)),
+            );
+            hex_digits.extend(
+                hex_literal_digits(line)
+                    .into_iter()
+                    .map(|(i, c)| (line_start + i, c)),
+            );
+        }
+        line_start += line.len();
+    }
+    if cleaned.is_empty() {
+        return Err(DecodeError::EmptyAfterStripping);
+    }
+    match hex_digits.iter().find(|&&(_, c)| !c.is_ascii_hexdigit()) {
+        None if !hex_digits.is_empty() => {
+            let (last_position, _) = hex_digits[hex_digits.len() - 1];
+            let text: String = hex_digits.iter().map(|&(_, c)| c).collect();
+            if !text.len().is_multiple_of(2) {
+                return Err(DecodeError::NotHex {
+                    position: last_position,
+                });
+            }
+            return hex::decode(&text).map_err(|_| DecodeError::NotHex {
+                position: last_position,
+            });
+        }
+        // Commas never appear in base64, so the input was meant as hex bytes.
+        Some(&(position, _)) if cleaned.iter().any(|&(_, c)| c == ',') => {
+            return Err(DecodeError::NotHex { position });
+        }
+        _ => {}
+    }
+    decode_base64(&cleaned)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Checks that `owned` mirrors `borrowed` node by node and that each
+    /// `length` is the number of content octets after the header in `input`.
+    fn assert_owned_matches(input: &[u8], borrowed: &ASN1Object, owned: &OwnedObject) {
+        assert_eq!(owned.tag, borrowed.tag);
+        assert_eq!(owned.length, borrowed.length);
+        assert_eq!(
+            (owned.tlv_start, owned.tlv_end),
+            (borrowed.tlv_start, borrowed.tlv_end)
+        );
+        let (_, _, contents) = split_tlv(&input[owned.tlv_start..owned.tlv_end]).unwrap();
+        match (&borrowed.value, &owned.value) {
+            (ASN1Value::Primitive(bytes), OwnedValue::Primitive(owned_bytes)) => {
+                assert_eq!(owned_bytes.as_slice(), *bytes);
+                assert_eq!(owned.length, contents.len());
+            }
+            (ASN1Value::Constructed(children), OwnedValue::Constructed(owned_children)) => {
+                assert_eq!(owned_children.len(), children.len());
+                for (b, o) in children.iter().zip(owned_children) {
+                    assert_owned_matches(input, b, o);
+                }
+                // Only an indefinite length leaves an end-of-contents marker.
+                let marker = if contents.len() > owned.length { 2 } else { 0 };
+                assert_eq!(owned.length + marker, contents.len());
+            }
+            _ => panic!("value kinds differ at offset {}", owned.tlv_start),
+        }
+    }
+
+    #[test]
+    fn test_owned_conversion_keeps_content_lengths() {
+        // SEQUENCE { INTEGER 5, SEQUENCE { OCTET STRING (128 bytes) } }, with
+        // long-form lengths on everything but the INTEGER.
+        let input = hex::decode(format!("308189020105308183048180{}", "AB".repeat(128))).unwrap();
+        let borrowed = DerParser::new(&input).parse_all().unwrap();
+        let owned: Vec<OwnedObject> = borrowed.iter().map(OwnedObject::from).collect();
+        assert_eq!(owned[0].length, 0x89);
+        assert_owned_matches(&input, &borrowed[0], &owned[0]);
+        // DER input re-encodes to itself, so every stored length is also the
+        // one the encoder (and the hex modal for edited nodes) computes.
+        assert_eq!(crate::der_encoder::to_der(&owned[0]), input);
+
+        // BER: SEQUENCE (indefinite) { INTEGER 5, SEQUENCE (indefinite) { NULL } }
+        let input = hex::decode("30800201053080050000000000").unwrap();
+        let config = ParserConfig {
+            mode: EncodingRules::Ber,
+            ..ParserConfig::default()
+        };
+        let borrowed = DerParser::with_config(&input, config).parse_all().unwrap();
+        let owned = OwnedObject::from(&borrowed[0]);
+        assert_eq!((owned.length, owned.tlv_end), (9, 13));
+        assert_owned_matches(&input, &borrowed[0], &owned);
+    }
+
+    #[test]
+    fn test_get_by_path() {
+        // SEQUENCE { INTEGER 1, SEQUENCE { INTEGER 2 } }, NULL
+        let mut objects = parse_owned(&hex::decode("300802010130030201020500").unwrap()).unwrap();
+        let number = |obj: Option<&OwnedObject>| obj.map(|o| o.tag.number);
+        assert_eq!(number(get_by_path(&objects, &[0])), Some(16));
+        assert_eq!(number(get_by_path(&objects, &[0, 1, 0])), Some(2));
+        assert_eq!(number(get_by_path(&objects, &[1])), Some(5));
+
+        // Out of range at the top level and below it.
+        assert_eq!(get_by_path(&objects, &[2]), None);
+        assert_eq!(get_by_path(&objects, &[0, 2]), None);
+        assert_eq!(get_by_path(&objects, &[]), None);
+        // Descending into a primitive.
+        assert_eq!(get_by_path(&objects, &[0, 0, 0]), None);
+        assert_eq!(get_by_path(&objects, &[1, 0]), None);
+
+        get_by_path_mut(&mut objects, &[0, 1, 0]).unwrap().value = OwnedValue::Primitive(vec![7]);
+        assert_eq!(
+            get_by_path(&objects, &[0, 1, 0]).unwrap().value,
+            OwnedValue::Primitive(vec![7])
+        );
+        assert!(get_by_path_mut(&mut objects, &[0, 0, 0]).is_none());
+    }
+
     #[test]
     fn test_read_byte() {
         let data = [0xDE, 0xAD, 0xBE, 0xEF];
@@ -240,6 +1051,291 @@ mod tests {
         assert_eq!(parser.read_n(1), None);
     }
 
+    #[test]
+    fn test_parse_all_lenient_keeps_objects_before_truncation() {
+        // INTEGER 5, NULL, then an INTEGER claiming two bytes but holding one.
+        let input = [0x02, 0x01, 0x05, 0x05, 0x00, 0x02, 0x02, 0x01];
+        let parsed = DerParser::new(&input).parse_all_lenient();
+        assert_eq!(parsed.objects.len(), 2);
+        assert_eq!(parsed.objects[1].tlv_end, 5);
+        assert_eq!(parsed.error, Some(ASN1Error::UnexpectedEOF { offset: 6 }));
+        assert!(DerParser::new(&input).parse_all().is_err());
+
+        let parsed = DerParser::new(&input[..5]).parse_all_lenient();
+        assert_eq!((parsed.objects.len(), parsed.error), (2, None));
+    }
+
+    #[test]
+    fn test_parse_with_spans_tiles_input() {
+        // SEQUENCE { INTEGER 1 }, OCTET STRING with a long-form length, NULL
+        let input = hex::decode(format!("3003020101048181{}0500", "AB".repeat(129))).unwrap();
+        let spans = parse_with_spans(&input).unwrap();
+        assert_eq!(spans.len(), 3);
+        let mut end = 0;
+        for (object, span) in &spans {
+            assert_eq!(span.start, end);
+            assert_eq!((object.tlv_start, object.tlv_end), (span.start, span.end));
+            end = span.end;
+        }
+        assert_eq!(end, input.len());
+        assert!(parse_with_spans(&input[..input.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_owned_parse_matches_converted_borrowed_parse() {
+        // SEQUENCE { INTEGER 1, SEQUENCE { NULL } } (BER, indefinite), NULL,
+        // then a truncated OCTET STRING.
+        let input = hex::decode("308002010130020500000005000403AA").unwrap();
+        for config in [ParserConfig::default(), ber_config()] {
+            let borrowed = DerParser::with_config(&input, config.clone()).parse_all_lenient();
+            let owned = DerParser::with_config(&input, config).parse_all_lenient_owned();
+            let converted: Vec<OwnedObject> =
+                borrowed.objects.iter().map(OwnedObject::from).collect();
+            assert_eq!(owned.objects, converted);
+            assert_eq!(owned.error, borrowed.error);
+        }
+        // The definite-length INTEGER and inner SEQUENCE on their own.
+        let der = &input[2..9];
+        assert_eq!(
+            parse_owned(der).unwrap(),
+            DerParser::new(der)
+                .parse_all()
+                .unwrap()
+                .iter()
+                .map(OwnedObject::from)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_iterator_matches_parse_all() {
+        let cert = try_decode_input(include_str!("../testCert.pem")).unwrap();
+        let input = [cert.as_slice(), &[0x02, 0x01, 0x05, 0x05, 0x00], &cert].concat();
+        let collected: Result<Vec<_>, _> = DerParser::new(&input).collect();
+        assert_eq!(collected, DerParser::new(&input).parse_all());
+        assert_eq!(collected.unwrap().len(), 4);
+
+        // A malformed TLV ends iteration after its error.
+        let mut parser = DerParser::new(&[0x05, 0x00, 0x30, 0x05, 0x02, 0x01]);
+        assert!(parser.next().unwrap().is_ok());
+        assert_eq!(
+            parser.next(),
+            Some(Err(ASN1Error::UnexpectedEOF { offset: 3 }))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_parse_single_rejects_trailing_data() {
+        let data = [0x30, 0x03, 0x02, 0x01, 0x01, 0x00];
+        let mut parser = DerParser::new(&data);
+        assert_eq!(
+            parser.parse_single(),
+            Err(ASN1Error::TrailingData { offset: 5 })
+        );
+
+        let mut parser = DerParser::new(&data[..5]);
+        let object = parser.parse_single().unwrap();
+        assert_eq!(object.tlv_end, 5);
+    }
+
+    #[test]
+    fn test_read_length_overflow() {
+        // Nine length octets cannot fit in a 64-bit usize.
+        let mut data = vec![0x89, 0x01];
+        data.extend([0x00; 8]);
+        let mut parser = DerParser::new(&data);
+        assert_eq!(parser.read_length(), None);
+
+        let mut data = vec![0x04, 0x89, 0x01];
+        data.extend([0x00; 8]);
+        let mut parser = DerParser::new(&data);
+        assert_eq!(
+            parser.parse_tlv(),
+            Err(ASN1Error::InvalidLength { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn test_length_larger_than_buffer() {
+        let data = [0x30, 0x05, 0x02, 0x01];
+        let mut parser = DerParser::new(&data);
+        assert_eq!(
+            parser.parse_tlv(),
+            Err(ASN1Error::UnexpectedEOF { offset: 1 })
+        );
+        // The failed read must not consume the partial value.
+        assert_eq!(parser.remaining(), 2);
+
+        let config = ParserConfig {
+            max_length: usize::MAX,
+            ..ParserConfig::default()
+        };
+        let data = [0x04, 0x84, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+        let mut parser = DerParser::with_config(&data, config);
+        assert_eq!(
+            parser.parse_tlv(),
+            Err(ASN1Error::UnexpectedEOF { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn test_decode_spaced_hex() {
+        assert_eq!(
+            try_decode_input("30 03\n02 01\t05\r\n"),
+            Ok(vec![0x30, 0x03, 0x02, 0x01, 0x05])
+        );
+    }
+
+    #[test]
+    fn test_decode_byte_array_literal() {
+        assert_eq!(
+            try_decode_input("0x30, 0x03,\n    0X02, 0x01, 0x05,"),
+            Ok(vec![0x30, 0x03, 0x02, 0x01, 0x05])
+        );
+        assert_eq!(
+            try_decode_input("[0x30, 0x03, 0x02, 0x01, 0x05]"),
+            Err(DecodeError::NotHex { position: 0 })
+        );
+        // A token that isn't hex keeps its 0x, so the error points at the x.
+        assert_eq!(
+            try_decode_input("0x30,0x0G"),
+            Err(DecodeError::NotHex { position: 6 })
+        );
+        // Only a token's leading 0x is stripped, so `300x` is read as base64.
+        assert_eq!(try_decode_input("300x"), Ok(vec![0xDF, 0x4D, 0x31]));
+        // Nor is it stripped from base64 that happens to start with 0x.
+        assert_eq!(
+            try_decode_input("0xQUJDQA"),
+            Ok(vec![0xD3, 0x14, 0x14, 0x24, 0x34, 0x00])
+        );
+    }
+
+    #[test]
+    fn test_decode_contiguous_hex() {
+        assert_eq!(
+            try_decode_input("3003020105"),
+            Ok(vec![0x30, 0x03, 0x02, 0x01, 0x05])
+        );
+        // A leading zero byte is not mistaken for a prefix.
+        assert_eq!(try_decode_input("0500"), Ok(vec![0x05, 0x00]));
+    }
+
+    #[test]
+    fn test_decode_base64_with_newlines() {
+        let pem = "-----BEGIN THING-----\nMAMC\nAQU=\n-----END THING-----\n";
+        assert_eq!(
+            try_decode_input(pem),
+            Ok(vec![0x30, 0x03, 0x02, 0x01, 0x05])
+        );
+    }
+
+    #[test]
+    fn test_decode_certificate_chain() {
+        let cert = include_str!("../testCert.pem");
+        let root = include_str!("../testCert2.pem");
+        let chain = format!("{}\n{}\n", cert, root);
+
+        let blocks = decode_pem_blocks(&chain).unwrap();
+        let labels: Vec<&str> = blocks.iter().map(|b| b.label.as_str()).collect();
+        assert_eq!(labels, vec!["CERTIFICATE", "CERTIFICATE"]);
+
+        // The first certificate's base64 ends in padding, which used to break
+        // the next block when both bodies were decoded as one.
+        let der = try_decode_input(&chain).unwrap();
+        let cert_der = try_decode_input(cert).unwrap();
+        assert_eq!(
+            der,
+            [cert_der.as_slice(), &try_decode_input(root).unwrap()].concat()
+        );
+        let objects = DerParser::new(&der).parse_all().unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[1].tlv_start, cert_der.len());
+    }
+
+    #[test]
+    fn test_decode_all_hex_prefers_hex() {
+        // "DEADBEEF" is also valid base64, but all-hex input is always hex.
+        assert_eq!(
+            try_decode_input("DEADBEEF"),
+            Ok(vec![0xDE, 0xAD, 0xBE, 0xEF])
+        );
+        // An odd digit count is reported rather than retried as base64.
+        assert_eq!(
+            try_decode_input("AB C\n"),
+            Err(DecodeError::NotHex { position: 3 })
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(try_decode_input(""), Err(DecodeError::Empty));
+        assert_eq!(try_decode_input(" \n\t"), Err(DecodeError::Empty));
+        assert_eq!(
+            try_decode_input("-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n"),
+            Err(DecodeError::EmptyAfterStripping)
+        );
+        assert_eq!(
+            try_decode_input("30 03\n02:01"),
+            Err(DecodeError::NotHex { position: 8 })
+        );
+        // A truncated base64 body.
+        assert_eq!(
+            try_decode_input("-----BEGIN CERTIFICATE-----\nMIIBIjANBgkq\nhkiG9w0\n"),
+            Err(DecodeError::NotBase64)
+        );
+        // A stray character inside a PEM body is a base64 error, not a hex one.
+        assert_eq!(
+            try_decode_input("-----BEGIN X-----\nMAMC:AQU=\n-----END X-----\n"),
+            Err(DecodeError::InvalidBase64 { position: 22 })
+        );
+    }
+
+    #[test]
+    fn test_decode_file_bytes() {
+        assert_eq!(
+            decode_file_bytes(b"3003020105\n".to_vec()),
+            vec![0x30, 0x03, 0x02, 0x01, 0x05]
+        );
+        assert_eq!(
+            decode_file_bytes(b"MAMCAQU=".to_vec()),
+            vec![0x30, 0x03, 0x02, 0x01, 0x05]
+        );
+        // Raw DER is passed through, whether or not it happens to be valid UTF-8.
+        let raw = vec![0x30, 0x03, 0x02, 0x01, 0x05];
+        assert_eq!(decode_file_bytes(raw.clone()), raw);
+        let raw = vec![0x04, 0x02, 0xFF, 0xFE];
+        assert_eq!(decode_file_bytes(raw.clone()), raw);
+    }
+
+    #[test]
+    fn test_constructed_length_shorter_than_children() {
+        // SEQUENCE { INTEGER 5, NULL } whose length stops inside the NULL.
+        let data = [0x30, 0x04, 0x02, 0x01, 0x05, 0x05, 0x00];
+        let err = DerParser::new(&data).parse_all().unwrap_err();
+        // The NULL's length octet lies past the SEQUENCE's end.
+        assert_eq!(err, ASN1Error::InvalidLength { offset: 6 });
+        assert_eq!(parse_owned(&data).unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_error_offsets_are_absolute_in_nested_values() {
+        // SEQUENCE { SEQUENCE { INTEGER with a truncated two-octet length } }
+        let data = [0x30, 0x05, 0x30, 0x03, 0x02, 0x82, 0x01];
+        let mut parser = DerParser::new(&data);
+        let err = parser.parse_all().unwrap_err();
+        assert_eq!(err, ASN1Error::InvalidLength { offset: 5 });
+        assert_eq!(err.to_string(), "invalid length at offset 5");
+
+        // SEQUENCE { INTEGER 1, OCTET STRING claiming 5 bytes but holding 1 }
+        let data = [0x30, 0x06, 0x02, 0x01, 0x01, 0x04, 0x05, 0x00];
+        let mut parser = DerParser::new(&data);
+        assert_eq!(
+            parser.parse_all(),
+            Err(ASN1Error::UnexpectedEOF { offset: 6 })
+        );
+    }
+
     #[test]
     fn test_peek_and_is_done() {
         let data = [0xAA];
@@ -259,7 +1355,7 @@ mod tests {
 
         let tag = parser.read_tag().unwrap();
         assert_eq!(tag.class, TagClass::Universal);
-        assert_eq!(tag.constructed, true);
+        assert!(tag.constructed);
         assert_eq!(tag.number, 16);
     }
 
@@ -270,10 +1366,295 @@ mod tests {
 
         let tag = parser.read_tag().unwrap();
         assert_eq!(tag.class, TagClass::Universal);
-        assert_eq!(tag.constructed, false);
+        assert!(!tag.constructed);
         assert_eq!(tag.number, 0x0281);
     }
 
+    #[test]
+    fn test_read_tag_rejects_oversized_number() {
+        // u32::MAX takes five base-128 groups.
+        let mut parser = DerParser::new(&[0x1F, 0x8F, 0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(parser.read_tag().unwrap().number, u32::MAX);
+        // One more group would need 39 bits.
+        let mut parser = DerParser::new(&[0x1F, 0x81, 0x80, 0x80, 0x80, 0x80, 0x00]);
+        assert_eq!(parser.read_tag(), None);
+        assert_eq!(
+            DerParser::new(&[0x1F, 0x90, 0x80, 0x80, 0x80, 0x00, 0x00]).parse_all(),
+            Err(ASN1Error::InvalidTag { offset: 0 })
+        );
+    }
+
+    #[test]
+    fn test_form_warning_primitive_sequence() {
+        let data = [0x10, 0x02, 0x30, 0x00]; // SEQUENCE tag with the constructed bit clear
+        let mut parser = DerParser::new(&data);
+        let obj = parser.parse_tlv().unwrap();
+
+        assert!(matches!(obj.value, ASN1Value::Primitive(_)));
+        assert_eq!(
+            obj.tag.form_warning(),
+            Some("SEQUENCE encoded as primitive".to_string())
+        );
+    }
+
+    #[test]
+    fn test_form_warning_constructed_integer() {
+        let data = [0x22, 0x03, 0x02, 0x01, 0x05]; // INTEGER tag with the constructed bit set
+        let mut parser = DerParser::new(&data);
+        let obj = parser.parse_tlv().unwrap();
+
+        assert_eq!(
+            obj.tag.form_warning(),
+            Some("INTEGER encoded as constructed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_form_warning_well_formed() {
+        let data = [0x30, 0x03, 0x02, 0x01, 0x05];
+        let mut parser = DerParser::new(&data);
+        let obj = parser.parse_tlv().unwrap();
+
+        assert_eq!(obj.tag.form_warning(), None);
+        match obj.value {
+            ASN1Value::Constructed(children) => assert_eq!(children[0].tag.form_warning(), None),
+            _ => panic!("Expected constructed value"),
+        }
+    }
+
+    #[test]
+    fn test_config_max_depth() {
+        // SEQUENCE { SEQUENCE { SEQUENCE {} } }
+        let data = [0x30, 0x04, 0x30, 0x02, 0x30, 0x00];
+        let config = ParserConfig {
+            max_depth: 2,
+            ..ParserConfig::default()
+        };
+        let mut parser = DerParser::with_config(&data, config);
+        assert_eq!(
+            parser.parse_all(),
+            Err(ASN1Error::MaxDepthExceeded { offset: 4 })
+        );
+
+        let config = ParserConfig {
+            max_depth: 3,
+            ..ParserConfig::default()
+        };
+        let mut parser = DerParser::with_config(&data, config);
+        assert!(parser.parse_all().is_ok());
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected_without_overflow() {
+        const LEVELS: usize = 100_000;
+        // Content lengths from the innermost SEQUENCE (around a NULL) outwards,
+        // so the headers can be written outside-in without re-copying.
+        let mut lengths = vec![2];
+        for _ in 1..LEVELS {
+            let inner = *lengths.last().unwrap();
+            lengths.push(1 + crate::der_encoder::encode_length(inner).len() + inner);
+        }
+        let mut data = Vec::new();
+        for &length in lengths.iter().rev() {
+            data.push(0x30);
+            data.extend(crate::der_encoder::encode_length(length));
+        }
+        data.extend([0x05, 0x00]);
+        assert!(matches!(
+            DerParser::new(&data).parse_all(),
+            Err(ASN1Error::MaxDepthExceeded { .. })
+        ));
+
+        // The same with BER indefinite lengths, which never reach their terminators.
+        let data = [0x30, 0x80].repeat(LEVELS);
+        let config = ParserConfig {
+            mode: EncodingRules::Ber,
+            ..ParserConfig::default()
+        };
+        assert_eq!(
+            DerParser::with_config(&data, config).parse_all(),
+            Err(ASN1Error::MaxDepthExceeded { offset: 2 * 128 })
+        );
+    }
+
+    #[test]
+    fn test_deep_nesting_parses_without_recursion() {
+        const LEVELS: usize = 5_000;
+        // SEQUENCE { SEQUENCE { ... } } with BER indefinite lengths, so the
+        // encoding is just the headers followed by the terminators.
+        let mut data = [0x30, 0x80].repeat(LEVELS);
+        data.extend([0x00, 0x00].repeat(LEVELS));
+        let config = ParserConfig {
+            mode: EncodingRules::Ber,
+            max_depth: usize::MAX,
+            ..ParserConfig::default()
+        };
+        let object = DerParser::with_config(&data, config)
+            .parse_single()
+            .unwrap();
+        assert_eq!(object.tlv_end, data.len());
+
+        let mut depth = 1;
+        let mut current = &object;
+        while let ASN1Value::Constructed(children) = &current.value {
+            let Some(child) = children.first() else { break };
+            assert_eq!(child.tlv_start, 2 * depth);
+            current = child;
+            depth += 1;
+        }
+        assert_eq!(depth, LEVELS);
+    }
+
+    #[test]
+    fn test_config_strict_lengths() {
+        // OCTET STRING with a long-form length for 5 bytes.
+        let data = [0x04, 0x82, 0x00, 0x05, 1, 2, 3, 4, 5];
+        let strict = ParserConfig {
+            strict_lengths: true,
+            ..ParserConfig::default()
+        };
+        assert_eq!(DerParser::new(&data).parse_all().unwrap()[0].length, 5);
+        assert_eq!(
+            DerParser::with_config(&data, strict.clone()).parse_all(),
+            Err(ASN1Error::InvalidLength { offset: 1 })
+        );
+
+        let mut parser = DerParser::with_config(&[0x81, 0x05], strict.clone());
+        assert_eq!(parser.read_length(), None);
+        // A leading zero octet is redundant even when the value needs long form.
+        let mut parser = DerParser::with_config(&[0x82, 0x00, 0x80], strict.clone());
+        assert_eq!(parser.read_length(), None);
+        let mut parser = DerParser::with_config(&[0x81, 0x80], strict);
+        assert_eq!(parser.read_length(), Some(0x80));
+    }
+
+    #[test]
+    fn test_config_max_length() {
+        let data = [0x04, 0x03, 0x01, 0x02, 0x03];
+        let config = ParserConfig {
+            max_length: 2,
+            ..ParserConfig::default()
+        };
+        let mut parser = DerParser::with_config(&data, config);
+        assert_eq!(
+            parser.parse_all(),
+            Err(ASN1Error::LengthLimitExceeded { offset: 1 })
+        );
+        assert!(DerParser::new(&data).parse_all().is_ok());
+    }
+
+    fn ber_config() -> ParserConfig {
+        ParserConfig {
+            mode: EncodingRules::Ber,
+            ..ParserConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_ber_nested_indefinite_sequence() {
+        let data = [
+            0x30, 0x80, // SEQUENCE, indefinite
+            0x30, 0x80, // SEQUENCE, indefinite
+            0x02, 0x01, 0x01, // INTEGER 1
+            0x00, 0x00, // end-of-contents
+            0x02, 0x01, 0x02, // INTEGER 2
+            0x00, 0x00, // end-of-contents
+        ];
+        let mut parser = DerParser::with_config(&data, ber_config());
+        let result = parser.parse_all().unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].length, 10);
+        assert_eq!((result[0].tlv_start, result[0].tlv_end), (0, 14));
+        match &result[0].value {
+            ASN1Value::Constructed(outer) => {
+                assert_eq!(outer.len(), 2);
+                assert_eq!(outer[0].length, 3);
+                assert_eq!((outer[0].tlv_start, outer[0].tlv_end), (2, 9));
+                assert_eq!((outer[1].tlv_start, outer[1].tlv_end), (9, 12));
+                assert_eq!(outer[1].tag.number, 2);
+                match &outer[0].value {
+                    ASN1Value::Constructed(inner) => {
+                        assert_eq!(inner.len(), 1);
+                        assert_eq!(inner[0].value, ASN1Value::Primitive(&[0x01]));
+                    }
+                    _ => panic!("Expected nested constructed value"),
+                }
+            }
+            _ => panic!("Expected constructed value"),
+        }
+    }
+
+    #[test]
+    fn test_ber_unterminated_indefinite() {
+        let data = [0x30, 0x80, 0x02, 0x01, 0x01];
+        let mut parser = DerParser::with_config(&data, ber_config());
+        assert_eq!(
+            parser.parse_all(),
+            Err(ASN1Error::UnexpectedEOF { offset: 5 })
+        );
+    }
+
+    #[test]
+    fn test_indefinite_rejected_for_primitive_and_der() {
+        let data = [0x04, 0x80, 0x01, 0x00, 0x00];
+        let mut parser = DerParser::with_config(&data, ber_config());
+        assert_eq!(
+            parser.parse_all(),
+            Err(ASN1Error::IndefiniteLengthNotAllowed { offset: 1 })
+        );
+
+        let data = [0x30, 0x80, 0x02, 0x01, 0x01, 0x00, 0x00];
+        let mut parser = DerParser::new(&data);
+        assert_eq!(
+            parser.parse_all(),
+            Err(ASN1Error::IndefiniteLengthNotAllowed { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn test_tlv_offsets_nested() {
+        let data = [
+            0x30, 0x0B, // SEQUENCE, length 11
+            0x02, 0x01, 0x01, // INTEGER 1
+            0x30, 0x06, // SEQUENCE, length 6
+            0x02, 0x01, 0x02, // INTEGER 2
+            0x02, 0x01, 0x03, // INTEGER 3
+        ];
+        let mut parser = DerParser::new(&data);
+        let result = parser.parse_all().unwrap();
+
+        let outer = &result[0];
+        assert_eq!((outer.tlv_start, outer.tlv_end), (0, 13));
+        let ASN1Value::Constructed(inner) = &outer.value else {
+            panic!("Expected constructed value");
+        };
+        assert_eq!((inner[0].tlv_start, inner[0].tlv_end), (2, 5));
+        assert_eq!((inner[1].tlv_start, inner[1].tlv_end), (5, 13));
+        let ASN1Value::Constructed(nested) = &inner[1].value else {
+            panic!("Expected nested constructed value");
+        };
+        assert_eq!(
+            &data[nested[1].tlv_start..nested[1].tlv_end],
+            &[0x02, 0x01, 0x03]
+        );
+    }
+
+    #[test]
+    fn test_tlv_offsets_non_minimal_length() {
+        // OCTET STRING with a long-form length of 2 that DER would encode in one byte.
+        let data = [0x02, 0x01, 0x00, 0x04, 0x81, 0x02, 0xAA, 0xBB];
+        let mut parser = DerParser::new(&data);
+        let result = parser.parse_all().unwrap();
+
+        let obj = &result[1];
+        assert_eq!((obj.tlv_start, obj.tlv_end), (3, 8));
+        let (tag, length, value) = split_tlv(&data[obj.tlv_start..obj.tlv_end]).unwrap();
+        assert_eq!(tag, &[0x04]);
+        assert_eq!(length, &[0x81, 0x02]);
+        assert_eq!(value, &[0xAA, 0xBB]);
+    }
+
     #[test]
     fn test_read_length_short() {
         let data = [0x0A]; // short-form: length = 10
@@ -1,16 +1,132 @@
 // src/format.rs
 
-use crate::der_parser::{ASN1Object, ASN1Value, OwnedObject, OwnedValue, TagClass};
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::ListItem;
-
-pub fn print_asn1_object(obj: &ASN1Object, indent: usize, pretty: bool) {
-    let indent_str = "  ".repeat(indent);
-    print_tag_header(obj, &indent_str);
-    print_tag_value(obj, &indent_str, pretty);
+use crate::der_parser::{ASN1Object, OwnedObject, OwnedValue, TagClass};
+use std::fmt::{self, Write};
+use std::sync::OnceLock;
+
+/// Primitive values longer than this are cut short in the dump unless
+/// `--max-value-bytes` says otherwise.
+pub const DEFAULT_MAX_VALUE_BYTES: usize = 64;
+
+/// Formats `bytes` as a `{:02X?}` list, keeping only the first `max` bytes
+/// (all of them when `max` is 0) and noting how many were left out.
+pub fn format_value_bytes(bytes: &[u8], max: usize) -> String {
+    if max == 0 || bytes.len() <= max {
+        format!("{:02X?}", bytes)
+    } else {
+        format!("{:02X?} … (+{} more)", &bytes[..max], bytes.len() - max)
+    }
+}
+
+/// Columns of indentation per nesting level unless `--indent` says otherwise.
+pub const DEFAULT_INDENT_WIDTH: usize = 2;
+
+/// Indentation per nesting level in the dump and the TUI tree (`--indent`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    /// One tab per level (`--indent tab`).
+    Tab,
 }
 
-pub fn print_tag_header(obj: &ASN1Object, indent_str: &str) {
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(DEFAULT_INDENT_WIDTH)
+    }
+}
+
+impl Indent {
+    /// The indentation in front of a line `depth` levels deep.
+    pub fn prefix(self, depth: usize) -> String {
+        match self {
+            Indent::Spaces(width) => " ".repeat(width * depth),
+            Indent::Tab => "\t".repeat(depth),
+        }
+    }
+}
+
+/// How [`render_tree_string`] lays out the dump.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    /// Whether primitive values are interpreted; when off they are printed
+    /// as raw byte lists.
+    pub pretty: bool,
+    /// Whether ANSI colors are written; the CLI turns them off for
+    /// `--no-color` and [`no_color_env`].
+    pub color: bool,
+    pub indent: Indent,
+    /// How many bytes of an OCTET STRING or unrecognized primitive are shown
+    /// before the rest is elided (`--max-value-bytes`); 0 shows everything.
+    pub max_value_bytes: usize,
+    /// The two-digit UTCTime year from which dates are read as 19xx rather
+    /// than 20xx (`--utc-year-pivot`).
+    pub utc_year_pivot: u32,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            color: false,
+            indent: Indent::default(),
+            max_value_bytes: DEFAULT_MAX_VALUE_BYTES,
+            utc_year_pivot: crate::asn1_time::RFC5280_YEAR_PIVOT,
+        }
+    }
+}
+
+/// Whether the `NO_COLOR` environment variable (https://no-color.org) is set
+/// to a non-empty value. Read once, on first use.
+pub fn no_color_env() -> bool {
+    static NO_COLOR: OnceLock<bool> = OnceLock::new();
+    *NO_COLOR.get_or_init(|| std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()))
+}
+
+/// `code` if `color` is set, otherwise an empty string. All ANSI escapes
+/// written by this module go through here.
+fn ansi(code: &'static str, color: bool) -> &'static str {
+    if color { code } else { "" }
+}
+
+/// Formats the objects as the indented dump printed by `--dump`, one line
+/// per tag and value, laid out as `options` says.
+pub fn render_tree_string(objects: &[OwnedObject], options: &RenderOptions) -> String {
+    let mut out = String::new();
+    for obj in objects {
+        // Writing to a String cannot fail.
+        let _ = write_object(&mut out, obj, 0, options);
+    }
+    out
+}
+
+pub fn print_asn1_object(obj: &ASN1Object, indent: usize, pretty: bool, color: bool) {
+    let options = RenderOptions {
+        pretty,
+        color,
+        ..RenderOptions::default()
+    };
+    let mut out = String::new();
+    let _ = write_object(&mut out, &OwnedObject::from(obj), indent, &options);
+    print!("{}", out);
+}
+
+fn write_object(
+    out: &mut String,
+    obj: &OwnedObject,
+    depth: usize,
+    options: &RenderOptions,
+) -> fmt::Result {
+    write_tag_header(out, obj, depth, options)?;
+    write_tag_value(out, obj, depth, options)
+}
+
+fn write_tag_header(
+    out: &mut String,
+    obj: &OwnedObject,
+    depth: usize,
+    options: &RenderOptions,
+) -> fmt::Result {
+    let color = options.color;
     let class_str = match &obj.tag.class {
         TagClass::Universal => "Universal",
         TagClass::Application => "Application",
@@ -25,14 +141,25 @@ pub fn print_tag_header(obj: &ASN1Object, indent_str: &str) {
         (TagClass::Universal, 4) => Some("OCTET STRING"),
         (TagClass::Universal, 5) => Some("NULL"),
         (TagClass::Universal, 6) => Some("OBJECT IDENTIFIER"),
+        (TagClass::Universal, 7) => Some("ObjectDescriptor"),
+        (TagClass::Universal, 9) => Some("REAL"),
         (TagClass::Universal, 10) => Some("ENUMERATED"),
+        (TagClass::Universal, 12) => Some("UTF8String"),
+        (TagClass::Universal, 13) => Some("RELATIVE-OID"),
         (TagClass::Universal, 16) => Some("SEQUENCE"),
         (TagClass::Universal, 17) => Some("SET"),
+        (TagClass::Universal, 18) => Some("NumericString"),
         (TagClass::Universal, 19) => Some("PrintableString"),
         (TagClass::Universal, 20) => Some("T61String"),
+        (TagClass::Universal, 21) => Some("VideotexString"),
         (TagClass::Universal, 22) => Some("IA5String"),
         (TagClass::Universal, 23) => Some("UTCTime"),
         (TagClass::Universal, 24) => Some("GeneralizedTime"),
+        (TagClass::Universal, 25) => Some("GraphicString"),
+        (TagClass::Universal, 26) => Some("VisibleString"),
+        (TagClass::Universal, 27) => Some("GeneralString"),
+        (TagClass::Universal, 28) => Some("UniversalString"),
+        (TagClass::Universal, 30) => Some("BMPString"),
         _ => None,
     };
 
@@ -42,66 +169,104 @@ pub fn print_tag_header(obj: &ASN1Object, indent_str: &str) {
         obj.tag.number.to_string()
     };
 
-    let tag_color = "\x1b[1;34m";
-    let reset = "\x1b[0m";
+    let tag_color = ansi("\x1b[1;34m", color);
+    let reset = ansi("\x1b[0m", color);
 
-    println!(
+    writeln!(
+        out,
         "{}{}Tag:{} class={}, constructed={}, number={}",
-        indent_str, tag_color, reset, class_str, obj.tag.constructed, tag_display
-    );
+        options.indent.prefix(depth),
+        tag_color,
+        reset,
+        class_str,
+        obj.tag.constructed,
+        tag_display
+    )?;
+
+    let value_warning = match &obj.value {
+        OwnedValue::Primitive(bytes) => crate::validate::primitive_warning(&obj.tag, bytes),
+        OwnedValue::Constructed(_) => None,
+    };
+    for warning in obj.tag.form_warning().into_iter().chain(value_warning) {
+        let warn_color = ansi("\x1b[1;33m", color);
+        let indent = options.indent.prefix(depth + 1);
+        writeln!(out, "{}{}⚠ {}{}", indent, warn_color, warning, reset)?;
+    }
+    Ok(())
 }
 
-pub fn print_tag_value(obj: &ASN1Object, indent_str: &str, pretty: bool) {
+fn write_tag_value(
+    out: &mut String,
+    obj: &OwnedObject,
+    depth: usize,
+    options: &RenderOptions,
+) -> fmt::Result {
+    // Values are written one level below their tag line.
+    let indent_str = &options.indent.prefix(depth + 1);
     match &obj.value {
-        ASN1Value::Primitive(bytes) => interpret_value(obj, indent_str, pretty, bytes),
-        ASN1Value::Constructed(children) => {
-            let tag_color = "\x1b[1;34m";
-            let reset = "\x1b[0m";
-            if pretty {
-                println!(
-                    "{}  {}Constructed:{} {} children:",
+        OwnedValue::Primitive(bytes) => interpret_value(out, obj, indent_str, options, bytes),
+        OwnedValue::Constructed(children) => {
+            let tag_color = ansi("\x1b[1;34m", options.color);
+            let reset = ansi("\x1b[0m", options.color);
+            if options.pretty {
+                writeln!(
+                    out,
+                    "{}{}Constructed:{} {} children:",
                     indent_str,
                     tag_color,
                     reset,
                     children.len()
-                );
+                )?;
                 for child in children {
-                    print_asn1_object(child, indent_str.len() / 2 + 1, pretty);
+                    write_object(out, child, depth + 1, options)?;
                 }
             } else {
                 for child in children {
-                    print_asn1_object(child, indent_str.len() / 2, pretty);
+                    write_object(out, child, depth, options)?;
                 }
             }
+            Ok(())
         }
     }
 }
 
-fn interpret_value(obj: &ASN1Object, indent_str: &str, pretty: bool, bytes: &[u8]) {
-    let tag_color = "\x1b[1;34m";
-    let reset = "\x1b[0m";
+fn interpret_value(
+    out: &mut String,
+    obj: &OwnedObject,
+    indent_str: &str,
+    options: &RenderOptions,
+    bytes: &[u8],
+) -> fmt::Result {
+    let tag_color = ansi("\x1b[1;34m", options.color);
+    let reset = ansi("\x1b[0m", options.color);
 
-    if !pretty {
-        println!("{:02X?}", bytes);
-        return;
+    if !options.pretty {
+        return writeln!(out, "{:02X?}", bytes);
     }
+    let max_bytes = options.max_value_bytes;
 
     match obj.tag.class {
         TagClass::Universal => match obj.tag.number {
             1 => {
-                let value = !bytes.is_empty() && bytes[0] != 0;
-                println!("{}  {}BOOLEAN:{} {}", indent_str, tag_color, reset, value);
+                let value = bytes.iter().any(|&b| b != 0);
+                writeln!(
+                    out,
+                    "{}{}BOOLEAN:{} {}",
+                    indent_str, tag_color, reset, value
+                )?;
             }
             2 => {
-                let value = num_bigint::BigUint::from_bytes_be(bytes);
-                println!(
-                    "{}  {}INTEGER:{} {} ({} bytes)",
+                let value = num_bigint::BigInt::from_signed_bytes_be(bytes);
+                writeln!(
+                    out,
+                    "{}{}INTEGER:{} {} ({} bytes, {}-bit)",
                     indent_str,
                     tag_color,
                     reset,
                     value,
-                    bytes.len()
-                );
+                    bytes.len(),
+                    value.bits()
+                )?;
             }
             3 => {
                 if let Some((&padding_bits, bits)) = bytes.split_first() {
@@ -109,183 +274,331 @@ fn interpret_value(obj: &ASN1Object, indent_str: &str, pretty: bool, bytes: &[u8
                         .len()
                         .saturating_mul(8)
                         .saturating_sub(padding_bits as usize);
-                    println!(
-                        "{}  {}BIT STRING:{} ({} bits, {} padding): {:02X?}",
+                    writeln!(
+                        out,
+                        "{}{}BIT STRING:{} ({} bits, {} padding): {:02X?}",
                         indent_str, tag_color, reset, bit_len, padding_bits, bits
-                    );
+                    )?;
+                    if bits.len() <= crate::decode::NAMED_BITS_MAX_BYTES
+                        && let Some((_, set)) = crate::decode::bit_string_bits(bytes)
+                    {
+                        writeln!(
+                            out,
+                            "{}{}bits set:{} {}",
+                            indent_str,
+                            tag_color,
+                            reset,
+                            crate::decode::format_bit_set(&set)
+                        )?;
+                    }
                 } else {
-                    println!("{}  {}BIT STRING:{} <empty>", indent_str, tag_color, reset);
+                    writeln!(
+                        out,
+                        "{}{}BIT STRING:{} <empty>",
+                        indent_str, tag_color, reset
+                    )?;
                 }
             }
             4 => {
-                println!(
-                    "{}  {}OCTET STRING:{} ({} bytes): {:02X?}",
+                writeln!(
+                    out,
+                    "{}{}OCTET STRING:{} ({} bytes): {}",
                     indent_str,
                     tag_color,
                     reset,
                     bytes.len(),
-                    bytes
-                );
+                    format_value_bytes(bytes, max_bytes)
+                )?;
             }
-            5 => {
-                println!("{}  {}NULL:{} (0 bytes)", indent_str, tag_color, reset);
+            5 if bytes.is_empty() => {
+                writeln!(out, "{}{}NULL:{} (0 bytes)", indent_str, tag_color, reset)?;
             }
-            6 => {
-                if let Some(first) = bytes.first() {
-                    let mut oid = vec![(first / 40).to_string(), (first % 40).to_string()];
-                    let mut value: u32 = 0;
-                    for &b in &bytes[1..] {
-                        value = (value << 7) | (b & 0x7F) as u32;
-                        if b & 0x80 == 0 {
-                            oid.push(value.to_string());
-                            value = 0;
-                        }
-                    }
-                    println!(
-                        "{}  {}OID:{} {} ({} bytes)",
+            5 => writeln!(
+                out,
+                "{}{}NULL{} (invalid: {} content bytes)",
+                indent_str,
+                tag_color,
+                reset,
+                bytes.len()
+            )?,
+            6 => match crate::oid::decode_oid(bytes) {
+                Ok(oid) => writeln!(
+                    out,
+                    "{}{}OID:{} {} ({} bytes)",
+                    indent_str,
+                    tag_color,
+                    reset,
+                    crate::oid::describe_oid(&oid),
+                    bytes.len()
+                )?,
+                Err(crate::oid::OidError::Empty) => {
+                    writeln!(out, "{}{}OID:{} <empty>", indent_str, tag_color, reset)?
+                }
+                Err(e) => writeln!(
+                    out,
+                    "{}{}OID:{} <invalid: {:?}> ({:02X?})",
+                    indent_str, tag_color, reset, e, bytes
+                )?,
+            },
+            9 => match crate::decode::decode_real(bytes) {
+                Some(value) => writeln!(
+                    out,
+                    "{}{}REAL:{} {} ({} bytes)",
+                    indent_str,
+                    tag_color,
+                    reset,
+                    crate::decode::format_real(value),
+                    bytes.len()
+                )?,
+                None => writeln!(
+                    out,
+                    "{}{}REAL:{} <invalid> ({:02X?})",
+                    indent_str, tag_color, reset, bytes
+                )?,
+            },
+            10 => {
+                writeln!(
+                    out,
+                    "{}{}ENUMERATED:{} {}",
+                    indent_str,
+                    tag_color,
+                    reset,
+                    crate::decode::format_enumerated(bytes, &[])
+                )?;
+            }
+            13 => match crate::oid::decode_relative_oid(bytes) {
+                Ok(arcs) => writeln!(
+                    out,
+                    "{}{}RELATIVE-OID:{} {} ({} bytes)",
+                    indent_str,
+                    tag_color,
+                    reset,
+                    arcs,
+                    bytes.len()
+                )?,
+                Err(e) => writeln!(
+                    out,
+                    "{}{}RELATIVE-OID:{} <invalid: {:?}> ({:02X?})",
+                    indent_str, tag_color, reset, e, bytes
+                )?,
+            },
+            12 | 19 | 20 | 22 => match std::str::from_utf8(bytes) {
+                Ok(text) => writeln!(
+                    out,
+                    "{}{}String:{} '{}' ({} bytes)",
+                    indent_str,
+                    tag_color,
+                    reset,
+                    text,
+                    bytes.len()
+                )?,
+                Err(_) => writeln!(
+                    out,
+                    "{}{}String:{} <invalid UTF-8> ({:?})",
+                    indent_str, tag_color, reset, bytes
+                )?,
+            },
+            7 | 18 | 21 | 25..=28 => {
+                match crate::decode::decode_restricted_string(obj.tag.number, bytes) {
+                    Some(text) => writeln!(
+                        out,
+                        "{}{}String:{} '{}' ({} bytes)",
                         indent_str,
                         tag_color,
                         reset,
-                        oid.join("."),
+                        text,
                         bytes.len()
-                    );
-                } else {
-                    println!("{}  {}OID:{} <empty>", indent_str, tag_color, reset);
+                    )?,
+                    None => writeln!(
+                        out,
+                        "{}{}String:{} <invalid encoding> ({:?})",
+                        indent_str, tag_color, reset, bytes
+                    )?,
                 }
             }
-            19 | 20 | 22 => match std::str::from_utf8(bytes) {
-                Ok(text) => println!(
-                    "{}  {}String:{} '{}' ({} bytes)",
+            30 => match crate::decode::decode_bmp_string(bytes) {
+                Some(text) => writeln!(
+                    out,
+                    "{}{}String:{} '{}' ({} bytes)",
                     indent_str,
                     tag_color,
                     reset,
                     text,
                     bytes.len()
-                ),
-                Err(_) => println!(
-                    "{}  {}String:{} <invalid UTF-8> ({:?})",
+                )?,
+                None => writeln!(
+                    out,
+                    "{}{}String:{} <invalid BMPString> ({:?})",
                     indent_str, tag_color, reset, bytes
-                ),
+                )?,
             },
             23 | 24 => match std::str::from_utf8(bytes) {
-                Ok(time) => println!(
-                    "{}  {}Time:{} '{}' ({} bytes)",
-                    indent_str,
-                    tag_color,
-                    reset,
-                    time,
-                    bytes.len()
-                ),
-                Err(_) => println!(
-                    "{}  {}Time:{} <invalid UTF-8> ({:?})",
+                Ok(time) => {
+                    let normalized = crate::asn1_time::parse_asn1_time_with_pivot(
+                        bytes,
+                        obj.tag.number == 24,
+                        options.utc_year_pivot,
+                    )
+                    .map(|t| t.to_iso8601())
+                    .unwrap_or_else(|| "<invalid time>".to_string());
+                    writeln!(
+                        out,
+                        "{}{}Time:{} '{}' → {} ({} bytes)",
+                        indent_str,
+                        tag_color,
+                        reset,
+                        time,
+                        normalized,
+                        bytes.len()
+                    )?
+                }
+                Err(_) => writeln!(
+                    out,
+                    "{}{}Time:{} <invalid UTF-8> ({:?})",
                     indent_str, tag_color, reset, bytes
-                ),
+                )?,
             },
             _ => {
-                println!(
-                    "{}  {}Primitive:{} ({} bytes): {:02X?}",
+                writeln!(
+                    out,
+                    "{}{}Primitive:{} ({} bytes): {}",
                     indent_str,
                     tag_color,
                     reset,
                     bytes.len(),
-                    bytes
-                );
+                    format_value_bytes(bytes, max_bytes)
+                )?;
             }
         },
         _ => {
-            println!(
-                "{}  {}Primitive:{} ({} bytes): {:02X?}",
+            writeln!(
+                out,
+                "{}{}Primitive:{} ({} bytes): {}",
                 indent_str,
                 tag_color,
                 reset,
                 bytes.len(),
-                bytes
-            );
+                format_value_bytes(bytes, max_bytes)
+            )?;
         }
     }
+    Ok(())
 }
 
-/// Walks an OwnedObject tree and returns a flat Vec of ListItems,
-/// with indentation, tag names, lengths and values already formatted.
-pub fn tui_list_items(objects: &[OwnedObject], selected_path: &[usize]) -> Vec<ListItem<'static>> {
-    let mut items = Vec::new();
-    let mut path = Vec::new();
-
-    fn rec(
-        obj: &OwnedObject,
-        depth: usize,
-        path: &mut Vec<usize>,
-        selected_path: &[usize],
-        items: &mut Vec<ListItem<'static>>,
-    ) {
-        // reuse your CLI formatting logic to build the label:
-        let tag_name: Option<&str> = match (&obj.tag.class, obj.tag.number) {
-            (TagClass::Universal, 1) => Some("BOOLEAN"),
-            (TagClass::Universal, 2) => Some("INTEGER"),
-            (TagClass::Universal, 3) => Some("BIT STRING"),
-            (TagClass::Universal, 4) => Some("OCTET STRING"),
-            (TagClass::Universal, 5) => Some("NULL"),
-            (TagClass::Universal, 6) => Some("OBJECT IDENTIFIER"),
-            (TagClass::Universal, 10) => Some("ENUMERATED"),
-            (TagClass::Universal, 16) => Some("SEQUENCE"),
-            (TagClass::Universal, 17) => Some("SET"),
-            (TagClass::Universal, 19) => Some("PrintableString"),
-            (TagClass::Universal, 20) => Some("T61String"),
-            (TagClass::Universal, 22) => Some("IA5String"),
-            (TagClass::Universal, 23) => Some("UTCTime"),
-            (TagClass::Universal, 24) => Some("GeneralizedTime"),
-            _ => Some(""),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(hex_input: &str, pretty: bool, color: bool) -> String {
+        let objects = crate::der_parser::parse_owned(&hex::decode(hex_input).unwrap()).unwrap();
+        let options = RenderOptions {
+            pretty,
+            color,
+            ..RenderOptions::default()
         };
+        render_tree_string(&objects, &options)
+    }
 
-        // before you recurse, replace the old label logic with:
+    #[test]
+    fn test_render_tree_string_pretty() {
+        // SEQUENCE { INTEGER 5, UTF8String "hi" }
+        let text = render("30070201050C026869", true, false);
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            vec![
+                "Tag: class=Universal, constructed=true, number=16 (SEQUENCE)",
+                "  Constructed: 2 children:",
+                "  Tag: class=Universal, constructed=false, number=2 (INTEGER)",
+                "    INTEGER: 5 (1 bytes, 3-bit)",
+                "  Tag: class=Universal, constructed=false, number=12 (UTF8String)",
+                "    String: 'hi' (2 bytes)",
+            ]
+        );
+    }
 
-        let indent = "  ".repeat(depth);
+    #[test]
+    fn test_long_values_are_capped() {
+        let bytes: Vec<u8> = (0..100).collect();
+        let text = format_value_bytes(&bytes, 4);
+        assert_eq!(text, "[00, 01, 02, 03] … (+96 more)");
+        assert_eq!(format_value_bytes(&bytes[..4], 4), "[00, 01, 02, 03]");
+        assert_eq!(format_value_bytes(&bytes, 0), format!("{:02X?}", bytes));
 
-        // build a combined “name (number)” if we know the name, otherwise just the number
-        let tag_display = if let Some(name) = tag_name {
-            format!("{} ({})", name, obj.tag.number)
-        } else {
-            obj.tag.number.to_string()
+        // OCTET STRING of 5 bytes
+        let objects =
+            crate::der_parser::parse_owned(&hex::decode("04050102030405").unwrap()).unwrap();
+        let options = RenderOptions {
+            max_value_bytes: 2,
+            ..RenderOptions::default()
         };
+        assert!(render_tree_string(&objects, &options).contains("[01, 02] … (+3 more)"));
+    }
 
-        // finally glue them together with length
-        let mut label = format!("{}{} (len={})", indent, tag_display, obj.length);
-
-        // then if it’s a primitive, you can append “: value” as you already do
-        if let OwnedValue::Primitive(bytes) = &obj.value {
-            let v = if obj.tag.number == 2 {
-                num_bigint::BigUint::from_bytes_be(bytes).to_string()
-            } else {
-                format!("{:02X?}", bytes)
-            };
-            label.push_str(&format!(" : {}", v));
-        }
+    #[test]
+    fn test_render_tree_string_raw_and_colored() {
+        let raw = render("3003020105", false, false);
+        assert_eq!(
+            raw,
+            "Tag: class=Universal, constructed=true, number=16 (SEQUENCE)\n\
+             Tag: class=Universal, constructed=false, number=2 (INTEGER)\n\
+             [05]\n"
+        );
+        assert!(!raw.contains('\x1b'));
+        assert!(render("0500", true, true).contains("\x1b[1;34mNULL:\x1b[0m (0 bytes)"));
+    }
 
-        let is_selected = path == selected_path;
-        let style = if is_selected {
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-        };
+    #[test]
+    fn test_null_with_content_is_invalid() {
+        let text = render("050100", true, false);
+        assert!(
+            text.contains("  NULL (invalid: 1 content bytes)\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains("⚠ NULL must have no content bytes, found 1"),
+            "{text}"
+        );
+    }
 
-        items.push(ListItem::new(label).style(style));
+    #[test]
+    fn test_indent_width() {
+        assert_eq!(Indent::Spaces(4).prefix(3), " ".repeat(12));
+        assert_eq!(Indent::default().prefix(3), "      ");
+        assert_eq!(Indent::Tab.prefix(3), "\t\t\t");
+        assert_eq!(Indent::Spaces(4).prefix(0), "");
 
-        if let OwnedValue::Constructed(children) = &obj.value {
-            for (i, child) in children.iter().enumerate() {
-                path.push(i);
-                rec(child, depth + 1, path, selected_path, items);
-                path.pop();
-            }
-        }
+        // SEQUENCE { NULL }
+        let objects = crate::der_parser::parse_owned(&[0x30, 0x02, 0x05, 0x00]).unwrap();
+        let options = RenderOptions {
+            indent: Indent::Tab,
+            ..RenderOptions::default()
+        };
+        assert!(render_tree_string(&objects, &options).contains("\n\t\tNULL: (0 bytes)\n"));
     }
 
-    for (i, obj) in objects.iter().enumerate() {
-        path.push(i);
-        rec(obj, 0, &mut path, selected_path, &mut items);
-        path.pop();
+    #[test]
+    fn test_enumerated_shows_value() {
+        let text = render("0A0101", true, false);
+        assert!(text.contains("  ENUMERATED: 1\n"), "{text}");
     }
 
-    items
+    #[test]
+    fn test_utc_year_pivot() {
+        // UTCTime 490101000000Z and 500101000000Z
+        let year = |hex_input: &str, pivot: u32| {
+            let objects = crate::der_parser::parse_owned(&hex::decode(hex_input).unwrap()).unwrap();
+            let options = RenderOptions {
+                utc_year_pivot: pivot,
+                ..RenderOptions::default()
+            };
+            let text = render_tree_string(&objects, &options);
+            text.split("→ ").nth(1).unwrap()[..4].to_string()
+        };
+        let y49 = "170D3439303130313030303030305A";
+        let y50 = "170D3530303130313030303030305A";
+        assert_eq!(year(y49, crate::asn1_time::RFC5280_YEAR_PIVOT), "2049");
+        assert_eq!(year(y50, crate::asn1_time::RFC5280_YEAR_PIVOT), "1950");
+        assert_eq!(year(y49, 40), "1949");
+        assert_eq!(year(y50, 60), "2050");
+    }
 }
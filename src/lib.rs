@@ -0,0 +1,13 @@
+// src/lib.rs
+pub mod asn1_time;
+pub mod decode;
+pub mod der_encoder;
+pub mod der_parser;
+pub mod format;
+pub mod oid;
+pub mod schema;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod tui;
+pub mod validate;
+pub mod x509;
@@ -1,41 +1,209 @@
 // src/main.rs
-mod der_parser;
-mod format;
-pub mod tui;
-
+use asn1smith::der_parser::{decode_file_bytes, parse_owned};
+use asn1smith::format::{Indent, RenderOptions, no_color_env, render_tree_string};
+use asn1smith::tui::app::App;
+use asn1smith::tui::terminal::{TerminalGuard, install_panic_hook, restore_terminal};
 use crossterm::event::{self, Event};
 use crossterm::{
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{EnterAlternateScreen, enable_raw_mode},
 };
 use ratatui::prelude::*;
-use std::io::stdout;
+use std::io::{IsTerminal, Read, stdout};
+use std::path::PathBuf;
 use std::time::Duration;
-use tui::app::App;
+
+/// Prints the parsed tree of each of `paths` (or stdin when there are none)
+/// with the CLI formatter and exits, without entering the TUI. Several trees
+/// are each preceded by a `==> path <==` line.
+fn dump(paths: Vec<PathBuf>, options: &RenderOptions) -> ! {
+    if paths.len() < 2 {
+        dump_one(paths.into_iter().next(), options);
+        std::process::exit(0);
+    }
+    for (i, path) in paths.into_iter().enumerate() {
+        let separator = if i == 0 { "" } else { "\n" };
+        println!("{}==> {} <==", separator, path.display());
+        dump_one(Some(path), options);
+    }
+    std::process::exit(0);
+}
+
+/// Prints the parsed tree of `path` (or stdin when `None` or `-`), exiting
+/// with an error if it can't be read or parsed.
+fn dump_one(path: Option<PathBuf>, options: &RenderOptions) {
+    let (name, read) = match path.filter(|p| p.as_os_str() != "-") {
+        Some(path) => (path.display().to_string(), std::fs::read(&path)),
+        None => {
+            let mut bytes = Vec::new();
+            let read = std::io::stdin().read_to_end(&mut bytes).map(|_| bytes);
+            ("<stdin>".to_string(), read)
+        }
+    };
+    let bytes = match read {
+        Ok(bytes) => decode_file_bytes(bytes),
+        Err(err) => {
+            eprintln!("asn1smith: cannot read {}: {}", name, err);
+            std::process::exit(1);
+        }
+    };
+    match parse_owned(&bytes) {
+        Ok(objects) => print!("{}", render_tree_string(&objects, options)),
+        Err(err) => {
+            eprintln!("asn1smith: cannot parse {}: {}", name, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Parses the value following `flag` (or of an environment variable named
+/// `flag`), exiting with an error naming what was `expected` if it is
+/// missing, malformed, or rejected by `valid`.
+fn flag_value<T: std::str::FromStr>(
+    value: Option<std::ffi::OsString>,
+    flag: &str,
+    expected: &str,
+    valid: fn(&T) -> bool,
+) -> T {
+    match value.and_then(|v| v.to_str()?.parse().ok()) {
+        Some(value) if valid(&value) => value,
+        _ => {
+            eprintln!("asn1smith: {} expects {}", flag, expected);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Parses the environment variable `name` when it is set, exiting like
+/// [`flag_value`] if its value is malformed or rejected by `valid`.
+fn env_value<T: std::str::FromStr>(name: &str, expected: &str, valid: fn(&T) -> bool) -> Option<T> {
+    std::env::var_os(name).map(|value| flag_value(Some(value), name, expected, valid))
+}
+
+const USAGE: &str = "usage: asn1smith [--dump|-d] [--no-color] [--max-value-bytes N] \
+[--utc-year-pivot YY] [--indent N|tab] [PATH|-]...";
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dump_mode = false;
+    let mut no_color = false;
+    let mut render_options = RenderOptions::default();
+    let mut input_paths = Vec::new();
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.to_str() {
+            Some("--dump" | "-d") => dump_mode = true,
+            Some("--no-color") => no_color = true,
+            Some("--max-value-bytes") => {
+                render_options.max_value_bytes = flag_value(
+                    args.next(),
+                    "--max-value-bytes",
+                    "a number of bytes",
+                    |_| true,
+                );
+            }
+            Some("--utc-year-pivot") => {
+                render_options.utc_year_pivot = flag_value(
+                    args.next(),
+                    "--utc-year-pivot",
+                    "a two-digit year from 0 to 100",
+                    |&pivot| pivot <= 100,
+                );
+            }
+            Some("--indent") => {
+                let value = args.next();
+                render_options.indent = if value.as_deref().and_then(|v| v.to_str()) == Some("tab")
+                {
+                    Indent::Tab
+                } else {
+                    Indent::Spaces(flag_value(
+                        value,
+                        "--indent",
+                        "a number of spaces or \"tab\"",
+                        |&w| w > 0,
+                    ))
+                };
+            }
+            Some(flag) if flag.starts_with('-') && flag != "-" => {
+                eprintln!("asn1smith: unknown option {}\n{}", flag, USAGE);
+                std::process::exit(1);
+            }
+            _ => input_paths.push(PathBuf::from(arg)),
+        }
+    }
+    // Piped input (`cat cert.der | asn1smith`) has no terminal to draw on.
+    if dump_mode || (input_paths.is_empty() && !std::io::stdin().is_terminal()) {
+        let options = RenderOptions {
+            color: !no_color && !no_color_env(),
+            ..render_options
+        };
+        dump(input_paths, &options);
+    }
+
+    let mut app = App::new();
+    app.render_options = render_options;
+    app.fold_depth = env_value("ASN1SMITH_COLLAPSE_DEPTH", "a number of levels", |_| true);
+    if let Some(ms) = env_value(
+        "ASN1SMITH_POLL_MS",
+        "a positive number of milliseconds",
+        |&ms| ms > 0,
+    ) {
+        app.poll_interval = Duration::from_millis(ms);
+    }
+    if let Some(width) = env_value(
+        "ASN1SMITH_VALUE_WIDTH",
+        "a positive number of characters",
+        |&w| w > 0,
+    ) {
+        app.value_preview_width = width;
+    }
+    for path in input_paths {
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                eprintln!("asn1smith: cannot read {}: {}", path.display(), err);
+                std::process::exit(1);
+            }
+        };
+        if !app.open_file(path.display().to_string(), decode_file_bytes(bytes)) {
+            let reason = app.last_error.as_deref().unwrap_or("");
+            eprintln!("asn1smith: cannot parse {}: {}", path.display(), reason);
+            std::process::exit(1);
+        }
+    }
+
+    install_panic_hook();
     enable_raw_mode()?;
+    let guard = TerminalGuard::new(restore_terminal);
     let mut stdout = stdout();
     execute!(stdout, EnterAlternateScreen)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
+    if let Ok(label) = std::env::var("ASN1SMITH_PEM_LABEL")
+        && !label.is_empty()
+    {
+        app.pem_label = label;
+    }
     let res: Result<(), std::io::Error> = loop {
-        terminal.draw(|f| app.draw(f))?;
-        if event::poll(Duration::from_millis(250))? {
-            if let Event::Key(key) = event::read()? {
-                app.handle_input(key);
+        // Only redraw when something changed; an idle TUI shouldn't spin.
+        if app.needs_redraw {
+            terminal.draw(|f| app.draw(f))?;
+            app.needs_redraw = false;
+        }
+        if event::poll(app.poll_interval)? {
+            match event::read()? {
+                Event::Key(key) => app.handle_input(key),
+                Event::Resize(_, _) | Event::Paste(_) => app.needs_redraw = true,
+                _ => {}
             }
         }
+        app.expire_copy_confirmation();
         if app.should_quit {
             break Ok(());
         }
     };
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    drop(guard);
 
     if let Err(err) = res {
         println!("Error: {:?}", err);
@@ -0,0 +1,303 @@
+// src/oid.rs
+
+use num_bigint::BigUint;
+
+#[derive(Debug, PartialEq)]
+pub enum OidError {
+    Empty,
+    /// A sub-identifier started with a 0x80 byte (redundant leading zero group).
+    NonMinimal,
+    /// The last byte still had its continuation bit set.
+    Truncated,
+}
+
+/// Splits OID content bytes into base-128 sub-identifiers.
+/// Arcs are accumulated into `BigUint` so arbitrarily large values never wrap.
+pub fn decode_subidentifiers(bytes: &[u8]) -> Result<Vec<BigUint>, OidError> {
+    if bytes.is_empty() {
+        return Err(OidError::Empty);
+    }
+    let mut arcs = Vec::new();
+    let mut value = BigUint::default();
+    let mut at_start = true;
+    for &b in bytes {
+        if at_start && b == 0x80 {
+            return Err(OidError::NonMinimal);
+        }
+        value = (value << 7u32) | BigUint::from(b & 0x7F);
+        at_start = b & 0x80 == 0;
+        if at_start {
+            arcs.push(std::mem::take(&mut value));
+        }
+    }
+    if !at_start {
+        return Err(OidError::Truncated);
+    }
+    Ok(arcs)
+}
+
+/// Decodes OBJECT IDENTIFIER content bytes into dotted form, e.g. `1.2.840.113549.1.1.11`.
+/// The first sub-identifier encodes the first two arcs as `40 * X + Y`, where X is
+/// 0, 1, or 2 and only arc 2 may have a second arc of 40 or more.
+pub fn decode_oid(bytes: &[u8]) -> Result<String, OidError> {
+    let arcs: Vec<String> = oid_arcs(bytes)?.iter().map(|arc| arc.to_string()).collect();
+    Ok(arcs.join("."))
+}
+
+/// Decodes OBJECT IDENTIFIER content bytes into arcs, splitting the first
+/// sub-identifier into the first two arcs as [`decode_oid`] describes.
+pub fn oid_arcs(bytes: &[u8]) -> Result<Vec<BigUint>, OidError> {
+    let mut arcs = decode_subidentifiers(bytes)?.into_iter();
+    let first = arcs.next().ok_or(OidError::Empty)?;
+    let (x, y) = if first < BigUint::from(40u32) {
+        (BigUint::from(0u32), first)
+    } else if first < BigUint::from(80u32) {
+        (BigUint::from(1u32), first - 40u32)
+    } else {
+        (BigUint::from(2u32), first - 80u32)
+    };
+    Ok([x, y].into_iter().chain(arcs).collect())
+}
+
+/// Checks the first two arcs of an OID against X.660: the first arc must be
+/// 0, 1, or 2, and under 0 or 1 the second arc must be 0–39. Arcs from
+/// [`oid_arcs`] always satisfy this, since the `40 * X + Y` split cannot
+/// produce anything else; typed dotted text (see [`encode_dotted_oid`]) may
+/// not.
+pub fn arc_violation(arcs: &[BigUint]) -> Option<&'static str> {
+    match arcs {
+        [first, ..] if *first > BigUint::from(2u32) => Some("invalid first arc"),
+        [first, second, ..] if *first < BigUint::from(2u32) && *second >= BigUint::from(40u32) => {
+            Some("invalid second arc")
+        }
+        _ => None,
+    }
+}
+
+/// Encodes dotted text such as `1.2.840.113549` as OBJECT IDENTIFIER content
+/// bytes, rejecting OIDs with fewer than two arcs or whose first two arcs
+/// break [`arc_violation`]'s rules.
+pub fn encode_dotted_oid(text: &str) -> Result<Vec<u8>, String> {
+    let arcs = text
+        .trim()
+        .split('.')
+        .map(|arc| {
+            arc.parse::<BigUint>()
+                .map_err(|_| format!("{:?} is not a dotted OID", text.trim()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if arcs.len() < 2 {
+        return Err("an OID needs at least two arcs".to_string());
+    }
+    if let Some(violation) = arc_violation(&arcs) {
+        return Err(format!("{} in {}", violation, text.trim()));
+    }
+    let first = &arcs[0] * 40u32 + &arcs[1];
+    let mut bytes = Vec::new();
+    for arc in std::iter::once(&first).chain(&arcs[2..]) {
+        let groups = arc.to_radix_be(128);
+        let last = groups.len() - 1;
+        bytes.extend(
+            groups
+                .iter()
+                .enumerate()
+                .map(|(i, &g)| if i < last { g | 0x80 } else { g }),
+        );
+    }
+    Ok(bytes)
+}
+
+/// Decodes RELATIVE-OID content bytes into dotted form, e.g. `8571.3.2`.
+/// Unlike `decode_oid`, every sub-identifier is a single arc.
+pub fn decode_relative_oid(bytes: &[u8]) -> Result<String, OidError> {
+    let arcs: Vec<String> = decode_subidentifiers(bytes)?
+        .iter()
+        .map(|arc| arc.to_string())
+        .collect();
+    Ok(arcs.join("."))
+}
+
+/// Returns the conventional name of a well-known OID given in dotted form.
+pub fn oid_name(dotted: &str) -> Option<&'static str> {
+    let name = match dotted {
+        // PKCS #1 / RSA
+        "1.2.840.113549.1.1.1" => "rsaEncryption",
+        "1.2.840.113549.1.1.4" => "md5WithRSAEncryption",
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption",
+        "1.2.840.113549.1.1.10" => "rsassa-pss",
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption",
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption",
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption",
+        // PKCS #7 / #9
+        "1.2.840.113549.1.7.1" => "data",
+        "1.2.840.113549.1.7.2" => "signedData",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        "1.2.840.113549.1.9.14" => "extensionRequest",
+        // Elliptic curves and ECDSA
+        "1.2.840.10045.2.1" => "ecPublicKey",
+        "1.2.840.10045.3.1.7" => "prime256v1",
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256",
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384",
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512",
+        "1.3.132.0.34" => "secp384r1",
+        "1.3.132.0.35" => "secp521r1",
+        "1.3.101.112" => "Ed25519",
+        "1.3.101.113" => "Ed448",
+        // Hash algorithms
+        "1.3.14.3.2.26" => "sha1",
+        "2.16.840.1.101.3.4.2.1" => "sha256",
+        "2.16.840.1.101.3.4.2.2" => "sha384",
+        "2.16.840.1.101.3.4.2.3" => "sha512",
+        // X.520 attribute types
+        "2.5.4.3" => "commonName",
+        "2.5.4.4" => "surname",
+        "2.5.4.5" => "serialNumber",
+        "2.5.4.6" => "countryName",
+        "2.5.4.7" => "localityName",
+        "2.5.4.8" => "stateOrProvinceName",
+        "2.5.4.9" => "streetAddress",
+        "2.5.4.10" => "organizationName",
+        "2.5.4.11" => "organizationalUnitName",
+        "2.5.4.12" => "title",
+        "2.5.4.42" => "givenName",
+        "0.9.2342.19200300.100.1.25" => "domainComponent",
+        // X.509 extensions
+        "2.5.29.14" => "subjectKeyIdentifier",
+        "2.5.29.15" => "keyUsage",
+        "2.5.29.17" => "subjectAltName",
+        "2.5.29.19" => "basicConstraints",
+        "2.5.29.31" => "cRLDistributionPoints",
+        "2.5.29.32" => "certificatePolicies",
+        "2.5.29.35" => "authorityKeyIdentifier",
+        "2.5.29.37" => "extKeyUsage",
+        "1.3.6.1.5.5.7.1.1" => "authorityInfoAccess",
+        "1.3.6.1.5.5.7.3.1" => "serverAuth",
+        "1.3.6.1.5.5.7.3.2" => "clientAuth",
+        "1.3.6.1.5.5.7.48.1" => "ocsp",
+        "1.3.6.1.5.5.7.48.2" => "caIssuers",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Returns the name of a well-known signature or public-key algorithm OID,
+/// the kind found at the start of an AlgorithmIdentifier; other known OIDs,
+/// such as curve names, give `None`.
+pub fn algorithm_name(dotted: &str) -> Option<&'static str> {
+    const ALGORITHM_ARCS: [&str; 4] = [
+        "1.2.840.113549.1.1.", // PKCS #1
+        "1.2.840.10045.2.",    // EC public key types
+        "1.2.840.10045.4.",    // ECDSA signatures
+        "1.3.101.",            // Edwards curves
+    ];
+    ALGORITHM_ARCS
+        .iter()
+        .any(|arc| dotted.starts_with(arc))
+        .then(|| oid_name(dotted))
+        .flatten()
+}
+
+/// Formats a dotted OID with its name appended when known, e.g.
+/// `1.2.840.113549.1.1.11 (sha256WithRSAEncryption)`.
+pub fn describe_oid(dotted: &str) -> String {
+    match oid_name(dotted) {
+        Some(name) => format!("{} ({})", dotted, name),
+        None => dotted.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_sha256_with_rsa_encryption() {
+        let bytes = [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B];
+        assert_eq!(decode_oid(&bytes).unwrap(), "1.2.840.113549.1.1.11");
+    }
+
+    #[test]
+    fn test_arc_violation() {
+        let arcs = |list: &[u32]| list.iter().map(|&a| BigUint::from(a)).collect::<Vec<_>>();
+        assert_eq!(arc_violation(&arcs(&[1, 2, 840])), None);
+        assert_eq!(arc_violation(&arcs(&[2, 999])), None);
+        assert_eq!(arc_violation(&arcs(&[3, 1])), Some("invalid first arc"));
+        assert_eq!(arc_violation(&arcs(&[0, 40])), Some("invalid second arc"));
+        assert_eq!(arc_violation(&arcs(&[1, 39, 5])), None);
+    }
+
+    #[test]
+    fn test_encode_dotted_oid() {
+        let rsa = encode_dotted_oid("1.2.840.113549.1.1.11").unwrap();
+        assert_eq!(rsa, hex::decode("2A864886F70D01010B").unwrap());
+        assert_eq!(encode_dotted_oid("2.999.0"), Ok(vec![0x88, 0x37, 0x00]));
+        assert_eq!(
+            decode_oid(&encode_dotted_oid("2.40").unwrap()).as_deref(),
+            Ok("2.40")
+        );
+        assert_eq!(
+            encode_dotted_oid("1.40.5"),
+            Err("invalid second arc in 1.40.5".to_string())
+        );
+        assert_eq!(
+            encode_dotted_oid("3.1"),
+            Err("invalid first arc in 3.1".to_string())
+        );
+        assert_eq!(
+            encode_dotted_oid("1"),
+            Err("an OID needs at least two arcs".to_string())
+        );
+        assert!(encode_dotted_oid("1.2.x").is_err());
+    }
+
+    #[test]
+    fn test_decode_second_arc_above_39() {
+        // 2.999 encodes its first sub-identifier as 1079 = 0x88 0x37.
+        assert_eq!(decode_oid(&[0x88, 0x37, 0x03]).unwrap(), "2.999.3");
+    }
+
+    #[test]
+    fn test_decode_oversized_arc() {
+        // 2 * 128^10 does not fit in a u64.
+        let mut bytes = vec![0x2A, 0x82];
+        bytes.extend([0x80; 9]);
+        bytes.push(0x00);
+        assert_eq!(decode_oid(&bytes).unwrap(), "1.2.2361183241434822606848");
+    }
+
+    #[test]
+    fn test_decode_relative_oid() {
+        // The X.690 example { 8571 3 2 }: no 40 * X + Y split on the first arc.
+        assert_eq!(
+            decode_relative_oid(&[0xC2, 0x7B, 0x03, 0x02]).unwrap(),
+            "8571.3.2"
+        );
+        assert_eq!(decode_relative_oid(&[0x2A]).unwrap(), "42");
+        assert_eq!(decode_relative_oid(&[0x83]), Err(OidError::Truncated));
+        assert_eq!(decode_relative_oid(&[]), Err(OidError::Empty));
+    }
+
+    #[test]
+    fn test_well_known_names() {
+        assert_eq!(
+            oid_name("1.2.840.113549.1.1.11"),
+            Some("sha256WithRSAEncryption")
+        );
+        assert_eq!(oid_name("1.2.840.113549.1.1.1"), Some("rsaEncryption"));
+        assert_eq!(oid_name("2.5.4.3"), Some("commonName"));
+        assert_eq!(oid_name("2.5.4.10"), Some("organizationName"));
+        assert_eq!(oid_name("1.2.3.4"), None);
+        assert_eq!(
+            describe_oid("1.2.840.113549.1.1.11"),
+            "1.2.840.113549.1.1.11 (sha256WithRSAEncryption)"
+        );
+        assert_eq!(describe_oid("1.2.3.4"), "1.2.3.4");
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed() {
+        assert_eq!(decode_oid(&[]), Err(OidError::Empty));
+        assert_eq!(decode_oid(&[0x2A, 0x80, 0x01]), Err(OidError::NonMinimal));
+        assert_eq!(decode_oid(&[0x2A, 0x86]), Err(OidError::Truncated));
+    }
+}
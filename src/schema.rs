@@ -0,0 +1,100 @@
+// src/schema.rs
+
+use crate::der_parser::{OwnedObject, OwnedValue};
+use std::collections::HashMap;
+
+/// Path step that matches every child of a node, e.g. each extension in a
+/// certificate's extension list.
+pub const ANY: usize = usize::MAX;
+
+/// A declarative table of field names for one kind of structure. Each field
+/// is a path below the object the schema is applied to (`[]` is the object
+/// itself) and its name; [`ANY`] in a path matches every child at that level.
+pub struct Schema {
+    /// Whether a top-level object has the shape this schema describes.
+    pub applies_to: fn(&OwnedObject) -> bool,
+    pub fields: &'static [(&'static [usize], &'static str)],
+}
+
+/// The schemas consulted for tree labels, tried in order; the first that
+/// applies to a top-level object names its fields.
+pub const BUILTIN_SCHEMAS: &[Schema] = &[crate::x509::CERTIFICATE_SCHEMA];
+
+/// Field names from the first of `schemas` that applies to each top-level
+/// object, keyed by tree path. Fields whose path does not exist in the tree
+/// are skipped.
+pub fn schema_labels(
+    objects: &[OwnedObject],
+    schemas: &[Schema],
+) -> HashMap<Vec<usize>, &'static str> {
+    fn walk(
+        obj: &OwnedObject,
+        pattern: &[usize],
+        path: &mut Vec<usize>,
+        name: &'static str,
+        out: &mut HashMap<Vec<usize>, &'static str>,
+    ) {
+        let Some((&step, rest)) = pattern.split_first() else {
+            out.insert(path.clone(), name);
+            return;
+        };
+        let OwnedValue::Constructed(children) = &obj.value else {
+            return;
+        };
+        for (i, child) in children.iter().enumerate() {
+            if step == ANY || step == i {
+                path.push(i);
+                walk(child, rest, path, name, out);
+                path.pop();
+            }
+        }
+    }
+    let mut labels = HashMap::new();
+    for (i, obj) in objects.iter().enumerate() {
+        let Some(schema) = schemas.iter().find(|s| (s.applies_to)(obj)) else {
+            continue;
+        };
+        for &(pattern, name) in schema.fields {
+            walk(obj, pattern, &mut vec![i], name, &mut labels);
+        }
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::der_parser::parse_owned;
+
+    fn is_sequence(obj: &OwnedObject) -> bool {
+        obj.tag.number == 16
+    }
+
+    const PAIR_LIST: Schema = Schema {
+        applies_to: is_sequence,
+        fields: &[
+            (&[], "pairs"),
+            (&[ANY], "pair"),
+            (&[ANY, 1], "value"),
+            (&[5], "missing"),
+        ],
+    };
+
+    #[test]
+    fn test_schema_labels_apply_patterns() {
+        // SEQUENCE { SEQUENCE { INTEGER 1, INTEGER 2 }, SEQUENCE { INTEGER 3 } }, NULL
+        let objects =
+            parse_owned(&hex::decode("300D300602010102010230030201030500").unwrap()).unwrap();
+        let labels = schema_labels(&objects, &[PAIR_LIST]);
+        assert_eq!(
+            labels,
+            HashMap::from([
+                (vec![0], "pairs"),
+                (vec![0, 0], "pair"),
+                (vec![0, 1], "pair"),
+                (vec![0, 0, 1], "value"),
+            ])
+        );
+        assert!(schema_labels(&objects, &[]).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+// src/serialize.rs
+//
+// `serde::Serialize` for parsed trees, enabled by the `serde` feature. Each
+// object becomes `{"class", "constructed", "number", "value"}`, where `value`
+// is an uppercase hex string for primitives and an array of children for
+// constructed values.
+
+use crate::der_parser::{OwnedObject, OwnedValue, Tag, TagClass};
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+impl Serialize for TagClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, name) = match self {
+            TagClass::Universal => (0, "Universal"),
+            TagClass::Application => (1, "Application"),
+            TagClass::ContextSpecific => (2, "ContextSpecific"),
+            TagClass::Private => (3, "Private"),
+        };
+        serializer.serialize_unit_variant("TagClass", index, name)
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Tag", 3)?;
+        state.serialize_field("class", &self.class)?;
+        state.serialize_field("constructed", &self.constructed)?;
+        state.serialize_field("number", &self.number)?;
+        state.end()
+    }
+}
+
+impl Serialize for OwnedValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            OwnedValue::Primitive(bytes) => serializer.serialize_str(&hex::encode_upper(bytes)),
+            OwnedValue::Constructed(children) => serializer.collect_seq(children),
+        }
+    }
+}
+
+impl Serialize for OwnedObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("class", &self.tag.class)?;
+        map.serialize_entry("constructed", &self.tag.constructed)?;
+        map.serialize_entry("number", &self.tag.number)?;
+        map.serialize_entry("value", &self.value)?;
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::der_parser::parse_owned;
+
+    #[test]
+    fn test_serialize_sequence() {
+        // SEQUENCE { INTEGER 5, [0] { NULL } }
+        let objects = parse_owned(&[0x30, 0x07, 0x02, 0x01, 0x05, 0xA0, 0x02, 0x05, 0x00]).unwrap();
+        assert_eq!(
+            serde_json::to_string(&objects[0]).unwrap(),
+            concat!(
+                r#"{"class":"Universal","constructed":true,"number":16,"value":["#,
+                r#"{"class":"Universal","constructed":false,"number":2,"value":"05"},"#,
+                r#"{"class":"ContextSpecific","constructed":true,"number":0,"value":["#,
+                r#"{"class":"Universal","constructed":false,"number":5,"value":""}]}]}"#,
+            )
+        );
+    }
+}
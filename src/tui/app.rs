@@ -1,6 +1,31 @@
 // src/tui/app.rs
-use crate::der_parser::OwnedObject;
-use std::collections::HashSet;
+use crate::der_parser::{ASN1Error, DecodeError, OwnedObject};
+use crate::format::RenderOptions;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default time the event loop waits for input before checking for work again.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the hex modal shows "Copied to clipboard!" after a copy.
+pub const COPY_CONFIRMATION_TIME: Duration = Duration::from_millis(1500);
+
+/// Puts text on the clipboard; [`crate::tui::export::system_clipboard`]
+/// unless a test swaps in a recorder.
+pub type ClipboardWriter = fn(String) -> Result<(), Box<dyn std::error::Error>>;
+
+/// Tree rows assumed for scrolling until the first draw measures the real area.
+pub const DEFAULT_TREE_HEIGHT: usize = 10;
+
+/// PEM label used for exports unless `ASN1SMITH_PEM_LABEL` says otherwise.
+pub const DEFAULT_PEM_LABEL: &str = "CERTIFICATE";
+
+/// Text encoding used when copying the selected TLV from the hex modal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardFormat {
+    Hex,
+    Base64,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AppMode {
@@ -8,18 +33,136 @@ pub enum AppMode {
     View,
 }
 
+/// A timestamped status line shown in the message pane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogMessage {
+    pub timestamp: SystemTime,
+    pub text: String,
+}
+
+impl LogMessage {
+    /// Formats the message as `[HH:MM:SS] text`, using UTC wall-clock time.
+    pub fn display_line(&self) -> String {
+        let secs = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+            % 86_400;
+        format!(
+            "[{:02}:{:02}:{:02}] {}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            self.text
+        )
+    }
+}
+
 pub struct App {
     pub mode: AppMode,
     pub input_buffer: String,
+    /// Byte offset of the Input panel cursor in `input_buffer`.
+    pub cursor: usize,
+    /// Why the last Ctrl-R could not decode `input_buffer`, shown in the Input panel.
+    pub input_error: Option<DecodeError>,
     pub should_quit: bool,
     pub buffer: Vec<u8>,
     pub parsed_objects: Vec<OwnedObject>,
+    /// Files opened from the command line, switched with `[`/`]`; empty when
+    /// the input was typed or pasted.
+    pub files: Vec<crate::tui::files::OpenFile>,
+    /// Index in `files` of the file shown in the tree.
+    pub active_file: usize,
+    /// Why the last parse stopped before the end of `buffer`; the tree ends
+    /// with a marker row saying where.
+    pub parse_stop: Option<ASN1Error>,
     pub selected_path: Vec<usize>,
     pub collapsed_nodes: HashSet<Vec<usize>>,
+    /// Universal tag numbers that primitives at these paths are decoded as
+    /// instead of their own tag (`D`).
+    pub decode_overrides: HashMap<Vec<usize>, u32>,
+    /// When set, every constructed node at this depth or deeper (top level is
+    /// depth 0) is collapsed, both by `-`/`+` and by a parse that changes the
+    /// tree's structure.
+    pub fold_depth: Option<usize>,
+    /// Maximum characters of a primitive value shown in a tree label.
+    pub value_preview_width: usize,
     pub show_help: bool,
     pub tree_scroll: usize,
+    /// Rows of the tree shown by the last draw, used for scrolling and paging.
+    pub tree_height: usize,
+    /// First key of a two-key View-mode sequence (e.g. `g` of `gg`).
+    pub pending_key: Option<char>,
     pub show_hex_modal: bool,
-    pub copy_hex_to_clipboard: bool, // New field
+    /// First hex dump row shown in the hex modal.
+    pub hex_scroll: usize,
+    /// Whether RDN SETs in names are labelled `CN=…` and AlgorithmIdentifiers
+    /// with their algorithm (toggled with `t`).
+    pub show_dn_labels: bool,
+    /// Whether primitive values are decoded in the tree; `p` switches to raw hex.
+    pub pretty: bool,
+    /// Whether single-primitive-child wrappers share a row with their child
+    /// (toggled with `I`).
+    pub inline_single_child: bool,
+    /// How `Y` lays out the copied `--dump` text; its indent also sets the
+    /// tree guides. Colors stay off, as the text goes to the clipboard.
+    pub render_options: RenderOptions,
+    /// Whether the `X` panel dumping the whole input buffer is shown.
+    pub show_buffer_hex: bool,
+    /// When Ctrl-C/Ctrl-B in the hex modal last copied the selected TLV;
+    /// the modal confirms it for [`COPY_CONFIRMATION_TIME`].
+    pub copy_confirmation: Option<Instant>,
+    /// Where `Y` and the hex modal copy to; tests swap in a recorder.
+    pub clipboard: ClipboardWriter,
+    /// Set once `Y` has copied the tree; shows "Copied tree" until the next key.
+    pub copied_tree: bool,
+    pub needs_redraw: bool,
+    pub poll_interval: Duration,
+    pub pinned_path: Option<Vec<usize>>,
+    pub show_compare_modal: bool,
+    pub show_validation_modal: bool,
+    pub messages: Vec<LogMessage>,
+    pub show_log: bool,
+    pub log_scroll: usize,
+    /// The most recent decode or parse failure, shown in a red status line
+    /// until the next successful parse.
+    pub last_error: Option<String>,
+    pub search_buffer: String,
+    /// Whether keystrokes currently go to the `/` search prompt.
+    pub search_active: bool,
+    /// Destination path typed into the `w`/`W` export prompt.
+    pub export_buffer: String,
+    /// Whether keystrokes currently go to the `w`/`W` export prompt.
+    pub export_active: bool,
+    /// Whether the export prompt writes raw DER (`W`) rather than PEM (`w`).
+    pub export_der: bool,
+    /// Byte offset typed into the `o`/`:` jump prompt.
+    pub offset_buffer: String,
+    /// Whether keystrokes currently go to the `o`/`:` jump prompt.
+    pub offset_active: bool,
+    /// New value typed into the `e` prompt for the selected primitive.
+    pub edit_buffer: String,
+    /// Whether keystrokes currently go to the `e` value edit prompt.
+    pub edit_active: bool,
+    /// Type name typed into the `D` decode-as prompt.
+    pub decode_as_buffer: String,
+    /// Whether keystrokes currently go to the `D` decode-as prompt.
+    pub decode_as_active: bool,
+    /// Session file path typed into the `Ctrl-S`/`Ctrl-O` prompt.
+    pub session_buffer: String,
+    /// Whether keystrokes currently go to the session path prompt.
+    pub session_active: bool,
+    /// Whether the session prompt saves (`Ctrl-S`) rather than loads (`Ctrl-O`).
+    pub session_save: bool,
+    /// Label used in the `-----BEGIN/END-----` lines of exported PEM files.
+    pub pem_label: String,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
@@ -27,15 +170,84 @@ impl App {
         Self {
             mode: AppMode::Input,
             input_buffer: String::new(),
+            cursor: 0,
+            input_error: None,
             should_quit: false,
             parsed_objects: Vec::new(),
+            files: Vec::new(),
+            active_file: 0,
+            parse_stop: None,
             selected_path: vec![],
             buffer: Vec::new(),
             collapsed_nodes: HashSet::new(),
+            decode_overrides: HashMap::new(),
+            fold_depth: None,
+            value_preview_width: crate::tui::tree::DEFAULT_VALUE_WIDTH,
             show_help: false,
             tree_scroll: 0,
+            tree_height: DEFAULT_TREE_HEIGHT,
+            pending_key: None,
             show_hex_modal: false,
-            copy_hex_to_clipboard: false, // Initialize
+            hex_scroll: 0,
+            show_dn_labels: true,
+            pretty: true,
+            inline_single_child: false,
+            render_options: RenderOptions::default(),
+            show_buffer_hex: false,
+            copy_confirmation: None,
+            clipboard: crate::tui::export::system_clipboard,
+            copied_tree: false,
+            needs_redraw: true,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            pinned_path: None,
+            show_compare_modal: false,
+            show_validation_modal: false,
+            messages: Vec::new(),
+            show_log: false,
+            log_scroll: 0,
+            last_error: None,
+            search_buffer: String::new(),
+            search_active: false,
+            export_buffer: String::new(),
+            export_active: false,
+            export_der: false,
+            offset_buffer: String::new(),
+            offset_active: false,
+            edit_buffer: String::new(),
+            edit_active: false,
+            decode_as_buffer: String::new(),
+            decode_as_active: false,
+            session_buffer: String::new(),
+            session_active: false,
+            session_save: false,
+            pem_label: DEFAULT_PEM_LABEL.to_string(),
         }
     }
+
+    /// Starts the app on an already-decoded buffer, e.g. from a file given on
+    /// the command line. Returns whether it parsed; on success the app opens in
+    /// View mode.
+    pub fn load_buffer(&mut self, bytes: Vec<u8>) -> bool {
+        self.buffer = bytes;
+        let loaded = self.parse_buffer();
+        if loaded {
+            self.mode = AppMode::View;
+        }
+        loaded
+    }
+
+    /// Logs a failure and keeps it on screen in the error status line.
+    pub fn report_error(&mut self, text: String) {
+        self.log(text.clone());
+        self.last_error = Some(text);
+    }
+
+    /// Appends a status line to the message pane and scrolls it to the newest entry.
+    pub fn log(&mut self, text: impl Into<String>) {
+        self.messages.push(LogMessage {
+            timestamp: SystemTime::now(),
+            text: text.into(),
+        });
+        self.log_scroll = 0;
+    }
 }
@@ -0,0 +1,185 @@
+// src/tui/decode_as.rs
+use crate::der_parser::{OwnedObject, OwnedValue, Tag, TagClass};
+use crate::tui::app::App;
+use crate::tui::tree::tag_name;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Parses a universal type typed into the `D` prompt: a tag number or a
+/// name from [`tag_name`], ignoring case and spaces (`OID` is accepted for
+/// OBJECT IDENTIFIER). SEQUENCE and SET are rejected since only primitive
+/// values can be re-interpreted.
+pub fn parse_universal_type(text: &str) -> Option<u32> {
+    let wanted: String = text.split_whitespace().collect::<String>().to_uppercase();
+    let number = match wanted.parse() {
+        Ok(number) => number,
+        Err(_) if wanted == "OID" => 6,
+        Err(_) => (0..=30).find(|&n| {
+            tag_name(&TagClass::Universal, n)
+                .is_some_and(|name| name.replace(' ', "").to_uppercase() == wanted)
+        })?,
+    };
+    let named = tag_name(&TagClass::Universal, number).is_some();
+    (named && !matches!(number, 16 | 17)).then_some(number)
+}
+
+/// `object` as it is displayed when its value is decoded as universal type
+/// `number` instead of its own tag.
+pub fn decoded_as(object: &OwnedObject, number: u32) -> OwnedObject {
+    OwnedObject {
+        tag: Tag {
+            class: TagClass::Universal,
+            constructed: false,
+            number,
+        },
+        ..object.clone()
+    }
+}
+
+impl App {
+    /// Opens the `D` prompt for the selected primitive, pre-filled with the
+    /// type it is currently decoded as, if overridden.
+    pub fn start_decode_as(&mut self) {
+        let Some(obj) = self.get_selected_object() else {
+            return;
+        };
+        if !matches!(obj.value, OwnedValue::Primitive(_)) {
+            self.report_error("Only primitive values can be decoded as another type".to_string());
+            return;
+        }
+        self.decode_as_buffer = self
+            .decode_overrides
+            .get(&self.selected_path)
+            .and_then(|&n| tag_name(&TagClass::Universal, n))
+            .unwrap_or_default()
+            .to_string();
+        self.decode_as_active = true;
+    }
+
+    /// Handles a key while the `D` prompt is open. An unknown type is
+    /// reported and the prompt stays open so it can be corrected.
+    pub(crate) fn handle_decode_as_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                let text = self.decode_as_buffer.clone();
+                if self.set_decode_override(&text) {
+                    self.decode_as_active = false;
+                    self.decode_as_buffer.clear();
+                }
+            }
+            KeyCode::Esc => {
+                self.decode_as_active = false;
+                self.decode_as_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.decode_as_buffer.pop();
+            }
+            KeyCode::Char(c) => self.decode_as_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Displays the selected node's value as the universal type named by
+    /// `text`, leaving its stored tag alone; empty text removes the override.
+    /// Returns false, after reporting why, when `text` names no primitive type.
+    pub fn set_decode_override(&mut self, text: &str) -> bool {
+        if text.trim().is_empty() {
+            self.decode_overrides.remove(&self.selected_path);
+            return true;
+        }
+        let Some(number) = parse_universal_type(text) else {
+            self.report_error(format!(
+                "Decode as failed: {:?} is not a primitive universal type",
+                text.trim()
+            ));
+            return false;
+        };
+        self.decode_overrides
+            .insert(self.selected_path.clone(), number);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::app::AppMode;
+    use crate::tui::tree::tests::app_from_hex;
+    use crate::tui::tree::{TreeView, tui_list_items};
+    use crossterm::event::KeyModifiers;
+    use ratatui::widgets::ListItem;
+
+    fn row(app: &App, index: usize) -> ListItem<'_> {
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            app.display_options(),
+        );
+        items[index].clone()
+    }
+
+    #[test]
+    fn test_parse_universal_type() {
+        assert_eq!(parse_universal_type("OID"), Some(6));
+        assert_eq!(parse_universal_type("object identifier"), Some(6));
+        assert_eq!(parse_universal_type("utf8string"), Some(12));
+        assert_eq!(parse_universal_type(" 2 "), Some(2));
+        assert_eq!(parse_universal_type("SEQUENCE"), None);
+        assert_eq!(parse_universal_type("8"), None);
+        assert_eq!(parse_universal_type("STRING"), None);
+    }
+
+    #[test]
+    fn test_octet_string_decoded_as_oid() {
+        // SEQUENCE { OCTET STRING 2A 86 48, NULL }
+        let mut app = app_from_hex("300704032A86480500");
+        app.mode = AppMode::View;
+        app.selected_path = vec![0, 0];
+        let mut press = |code| app.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+        press(KeyCode::Char('D'));
+        for c in "oid".chars() {
+            press(KeyCode::Char(c));
+        }
+        press(KeyCode::Enter);
+        // Moving away and back keeps the override.
+        press(KeyCode::Char('j'));
+        press(KeyCode::Char('k'));
+        assert!(!app.decode_as_active);
+        assert_eq!(app.selected_path, vec![0, 0]);
+        assert_eq!(
+            row(&app, 1),
+            ListItem::new("├─ OCTET STRING (4) [len 3] as OBJECT IDENTIFIER: 1.2.840")
+        );
+        assert_eq!(app.get_selected_object().unwrap().tag.number, 4);
+
+        // An empty type goes back to the node's own tag.
+        app.start_decode_as();
+        assert_eq!(app.decode_as_buffer, "OBJECT IDENTIFIER");
+        app.decode_as_buffer.clear();
+        app.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.decode_overrides.is_empty());
+    }
+
+    #[test]
+    fn test_decode_as_unknown_type_keeps_prompt_open() {
+        let mut app = app_from_hex("04032A8648");
+        app.start_decode_as();
+        app.decode_as_buffer = "SET".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.decode_as_active);
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Decode as failed: \"SET\" is not a primitive universal type")
+        );
+    }
+
+    #[test]
+    fn test_decode_as_rejects_constructed_node() {
+        let mut app = app_from_hex("3000");
+        app.start_decode_as();
+        assert!(!app.decode_as_active);
+        assert!(app.last_error.is_some());
+    }
+}
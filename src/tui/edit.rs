@@ -0,0 +1,644 @@
+// src/tui/edit.rs
+use crate::der_encoder::{encode_content, to_der_all};
+use crate::der_parser::{OwnedObject, OwnedValue, TagClass, get_by_path_mut, parse_owned};
+use crate::tui::app::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Returns the child list that holds the node at `parent_path + [i]`;
+/// an empty `parent_path` refers to the top-level objects.
+fn siblings_mut<'a>(
+    objects: &'a mut Vec<OwnedObject>,
+    parent_path: &[usize],
+) -> Option<&'a mut Vec<OwnedObject>> {
+    if parent_path.is_empty() {
+        return Some(objects);
+    }
+    get_by_path_mut(objects, parent_path)?.children_mut()
+}
+
+/// Shifts a stored path to account for a node being removed at `removed`.
+/// Returns `None` if the path pointed at the removed node or one of its descendants.
+fn shift_after_removal(path: &[usize], removed: &[usize]) -> Option<Vec<usize>> {
+    let depth = removed.len() - 1;
+    if path.len() <= depth || path[..depth] != removed[..depth] {
+        return Some(path.to_vec());
+    }
+    let mut shifted = path.to_vec();
+    match path[depth].cmp(&removed[depth]) {
+        std::cmp::Ordering::Less => {}
+        std::cmp::Ordering::Equal => return None,
+        std::cmp::Ordering::Greater => shifted[depth] -= 1,
+    }
+    Some(shifted)
+}
+
+/// Shifts a stored path to account for a node being inserted at `inserted`.
+fn shift_after_insertion(path: &[usize], inserted: &[usize]) -> Vec<usize> {
+    let depth = inserted.len() - 1;
+    let mut shifted = path.to_vec();
+    if path.len() > depth && path[..depth] == inserted[..depth] && path[depth] >= inserted[depth] {
+        shifted[depth] += 1;
+    }
+    shifted
+}
+
+/// The node inserted by "add child": a universal NULL, which is valid
+/// anywhere and easy to spot in the tree.
+fn placeholder_object() -> OwnedObject {
+    OwnedObject {
+        tag: crate::der_parser::Tag {
+            class: crate::der_parser::TagClass::Universal,
+            constructed: false,
+            number: 5,
+        },
+        length: 0,
+        value: OwnedValue::Primitive(vec![]),
+        tlv_start: 0,
+        tlv_end: 0,
+        wrapped: None,
+    }
+}
+
+/// How the `e` prompt shows and reads a primitive's value.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum EditFormat {
+    /// Signed decimal, re-encoded minimally.
+    Integer,
+    /// Text in the string type's own character encoding.
+    Text(u32),
+    /// Dotted OBJECT IDENTIFIER arcs, e.g. `1.2.840.113549`.
+    Oid,
+    /// Raw content bytes as hex.
+    Hex,
+}
+
+fn edit_format(obj: &OwnedObject) -> EditFormat {
+    if obj.tag.class != TagClass::Universal {
+        return EditFormat::Hex;
+    }
+    match obj.tag.number {
+        2 | 10 => EditFormat::Integer,
+        6 => EditFormat::Oid,
+        n @ (7 | 12 | 18 | 19 | 21 | 22 | 23 | 24 | 25..=28 | 30) => EditFormat::Text(n),
+        _ => EditFormat::Hex,
+    }
+}
+
+/// The text the edit prompt starts with for `bytes`.
+fn edit_text(format: EditFormat, bytes: &[u8]) -> String {
+    match format {
+        EditFormat::Integer if bytes.is_empty() => String::new(),
+        EditFormat::Integer => num_bigint::BigInt::from_signed_bytes_be(bytes).to_string(),
+        EditFormat::Text(30) => crate::decode::decode_bmp_string(bytes)
+            .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned()),
+        EditFormat::Text(n) => crate::decode::decode_restricted_string(n, bytes)
+            .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned()),
+        EditFormat::Oid => crate::oid::decode_oid(bytes).unwrap_or_default(),
+        EditFormat::Hex => hex::encode_upper(bytes),
+    }
+}
+
+/// Checks the text typed into the edit prompt and encodes it as content bytes.
+fn parse_edit_text(format: EditFormat, text: &str) -> Result<Vec<u8>, String> {
+    let ascii_only =
+        |allowed: fn(char) -> bool, type_name: &str| match text.chars().find(|&c| !allowed(c)) {
+            Some(c) => Err(format!("{:?} is not allowed in a {}", c, type_name)),
+            None => Ok(text.as_bytes().to_vec()),
+        };
+    match format {
+        EditFormat::Integer => text
+            .trim()
+            .parse::<num_bigint::BigInt>()
+            .map(|v| v.to_signed_bytes_be())
+            .map_err(|_| format!("{:?} is not a decimal integer", text.trim())),
+        EditFormat::Text(18) => ascii_only(|c| c.is_ascii_digit() || c == ' ', "NumericString"),
+        EditFormat::Text(19) => ascii_only(crate::decode::is_printable_char, "PrintableString"),
+        EditFormat::Text(22 | 23 | 24 | 27) => ascii_only(|c| c.is_ascii(), "ASCII string"),
+        EditFormat::Text(26) => ascii_only(|c| c.is_ascii_graphic() || c == ' ', "VisibleString"),
+        EditFormat::Text(28) => Ok(text
+            .chars()
+            .flat_map(|c| (c as u32).to_be_bytes())
+            .collect()),
+        EditFormat::Text(30) => match text.chars().find(|&c| c as u32 > 0xFFFF) {
+            Some(c) => Err(format!("{:?} is outside the BMP", c)),
+            None => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        },
+        EditFormat::Text(_) => Ok(text.as_bytes().to_vec()),
+        EditFormat::Oid => crate::oid::encode_dotted_oid(text),
+        EditFormat::Hex => {
+            let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+            hex::decode(&digits).map_err(|e| format!("invalid hex: {}", e))
+        }
+    }
+}
+
+/// Moves offsets reported relative to a re-parsed slice to where the slice
+/// sits in the source buffer, or clears them when its position is unknown.
+fn rebase_offsets(obj: &mut OwnedObject, base: Option<usize>) {
+    match base {
+        Some(base) => {
+            obj.tlv_start += base;
+            obj.tlv_end += base;
+        }
+        None => {
+            obj.tlv_start = 0;
+            obj.tlv_end = 0;
+        }
+    }
+    if let Some(children) = obj.children_mut() {
+        for child in children {
+            rebase_offsets(child, base);
+        }
+    }
+}
+
+/// Writes the objects a primitive OCTET/BIT STRING wraps back into its
+/// bytes, after a BIT STRING's (zero) unused-bits byte.
+fn sync_wrapped_bytes(obj: &mut OwnedObject) {
+    let Some(children) = &obj.wrapped else {
+        return;
+    };
+    let unused_bits: &[u8] = if obj.tag.number == 3 { &[0] } else { &[] };
+    obj.value = OwnedValue::Primitive([unused_bits, &to_der_all(children)].concat());
+}
+
+impl App {
+    /// Recomputes the content length of every ancestor of `path` after its
+    /// subtree changed. Their recorded source offsets no longer describe the
+    /// tree, so they are cleared and the hex view falls back to re-encoding.
+    pub(crate) fn mark_ancestors_modified(&mut self, path: &[usize]) {
+        for depth in (1..path.len()).rev() {
+            if let Some(obj) = get_by_path_mut(&mut self.parsed_objects, &path[..depth]) {
+                sync_wrapped_bytes(obj);
+                obj.length = encode_content(obj).len();
+                obj.tlv_start = 0;
+                obj.tlv_end = 0;
+            }
+        }
+    }
+
+    /// Inserts a placeholder NULL: as the last child when the selection has
+    /// children (or wraps objects), otherwise as the next sibling. The new node is selected.
+    pub fn add_child(&mut self) {
+        let new_path = match self.get_selected_object().map(OwnedObject::children) {
+            Some(Some(children)) => {
+                let mut p = self.selected_path.clone();
+                p.push(children.len());
+                p
+            }
+            Some(None) => {
+                let mut p = self.selected_path.clone();
+                if let Some(last) = p.last_mut() {
+                    *last += 1;
+                }
+                p
+            }
+            None if self.parsed_objects.is_empty() => vec![0],
+            None => return,
+        };
+        let Some((&idx, parent_path)) = new_path.split_last() else {
+            return;
+        };
+        let Some(siblings) = siblings_mut(&mut self.parsed_objects, parent_path) else {
+            return;
+        };
+        siblings.insert(idx, placeholder_object());
+        self.mark_ancestors_modified(&new_path);
+
+        self.collapsed_nodes = self
+            .collapsed_nodes
+            .iter()
+            .filter(|p| p.as_slice() != parent_path)
+            .map(|p| shift_after_insertion(p, &new_path))
+            .collect();
+        self.decode_overrides = std::mem::take(&mut self.decode_overrides)
+            .into_iter()
+            .map(|(p, n)| (shift_after_insertion(&p, &new_path), n))
+            .collect();
+        self.pinned_path = self
+            .pinned_path
+            .take()
+            .map(|p| shift_after_insertion(&p, &new_path));
+        self.selected_path = new_path;
+        self.log(format!("Added NULL at {:?}", self.selected_path));
+    }
+
+    /// Opens the `e` prompt pre-filled with the selected primitive's value.
+    pub fn start_value_edit(&mut self) {
+        let Some(obj) = self.get_selected_object() else {
+            return;
+        };
+        let OwnedValue::Primitive(bytes) = &obj.value else {
+            self.report_error("Only primitive values can be edited".to_string());
+            return;
+        };
+        self.edit_buffer = edit_text(edit_format(obj), bytes);
+        self.edit_active = true;
+    }
+
+    /// Handles a key while the `e` value edit prompt is open. Invalid input
+    /// is reported and the prompt stays open so it can be corrected.
+    pub(crate) fn handle_edit_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                let text = self.edit_buffer.clone();
+                if self.set_selected_value(&text) {
+                    self.edit_active = false;
+                    self.edit_buffer.clear();
+                }
+            }
+            KeyCode::Esc => {
+                self.edit_active = false;
+                self.edit_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.edit_buffer.pop();
+            }
+            KeyCode::Char(c) => self.edit_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Replaces the selected primitive's content with `text`, read the way
+    /// the edit prompt shows it. Returns whether the value was accepted.
+    pub fn set_selected_value(&mut self, text: &str) -> bool {
+        let path = self.selected_path.clone();
+        let Some(obj) = self.get_selected_object() else {
+            return false;
+        };
+        if !matches!(obj.value, OwnedValue::Primitive(_)) {
+            return false;
+        }
+        let bytes = match parse_edit_text(edit_format(obj), text) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.report_error(format!("Invalid value for {:?}: {}", path, e));
+                return false;
+            }
+        };
+        let Some(target) = get_by_path_mut(&mut self.parsed_objects, &path) else {
+            return false;
+        };
+        target.length = bytes.len();
+        target.value = OwnedValue::Primitive(bytes);
+        target.tlv_start = 0;
+        target.tlv_end = 0;
+        // The new bytes may not wrap the same objects, or any.
+        target.wrapped = None;
+        self.mark_ancestors_modified(&path);
+        self.log(format!("Edited value of {:?}", path));
+        true
+    }
+
+    /// Parses the bytes of the selected OCTET STRING or BIT STRING as DER and
+    /// shows the result as its children. The node keeps its primitive value
+    /// and so encodes to the same bytes; ancestors are left untouched.
+    pub fn reparse_selected(&mut self) {
+        let path = self.selected_path.clone();
+        let Some(obj) = self.get_selected_object().cloned() else {
+            return;
+        };
+        let OwnedValue::Primitive(bytes) = &obj.value else {
+            self.report_error("Only primitive OCTET/BIT STRINGs can be re-parsed".to_string());
+            return;
+        };
+        let skip = match (&obj.tag.class, obj.tag.number) {
+            (TagClass::Universal, 4) => 0,
+            (TagClass::Universal, 3) if bytes.first() == Some(&0) => 1,
+            (TagClass::Universal, 3) => {
+                self.report_error("BIT STRING with unused bits cannot wrap DER".to_string());
+                return;
+            }
+            _ => {
+                self.report_error("Only OCTET STRING and BIT STRING can be re-parsed".to_string());
+                return;
+            }
+        };
+        let base = (obj.tlv_end > 0).then(|| obj.tlv_end - obj.length + skip);
+        let mut children = match parse_owned(&bytes[skip..]) {
+            Ok(children) if !children.is_empty() => children,
+            Ok(_) => {
+                self.report_error(format!("Node {:?} has no content to parse", path));
+                return;
+            }
+            Err(e) => {
+                self.report_error(format!("Node {:?} does not wrap DER: {}", path, e));
+                return;
+            }
+        };
+        for child in &mut children {
+            rebase_offsets(child, base);
+        }
+        let Some(target) = get_by_path_mut(&mut self.parsed_objects, &path) else {
+            return;
+        };
+        let count = children.len();
+        target.wrapped = Some(children);
+        self.collapsed_nodes.remove(&path);
+        self.log(format!("Parsed {} wrapped object(s) in {:?}", count, path));
+    }
+
+    /// Removes the selected node and moves the selection to its previous
+    /// sibling, or to its parent when it was the first child.
+    pub fn delete_selected(&mut self) {
+        let path = self.selected_path.clone();
+        let Some((&idx, parent_path)) = path.split_last() else {
+            return;
+        };
+        let Some(siblings) = siblings_mut(&mut self.parsed_objects, parent_path) else {
+            return;
+        };
+        if idx >= siblings.len() {
+            return;
+        }
+        siblings.remove(idx);
+        self.mark_ancestors_modified(&path);
+
+        self.collapsed_nodes = self
+            .collapsed_nodes
+            .iter()
+            .filter_map(|p| shift_after_removal(p, &path))
+            .collect();
+        self.decode_overrides = std::mem::take(&mut self.decode_overrides)
+            .into_iter()
+            .filter_map(|(p, n)| Some((shift_after_removal(&p, &path)?, n)))
+            .collect();
+        self.pinned_path = self
+            .pinned_path
+            .take()
+            .and_then(|p| shift_after_removal(&p, &path));
+
+        self.selected_path = parent_path.to_vec();
+        if idx > 0 {
+            self.selected_path.push(idx - 1);
+        }
+        self.clamp_selection();
+        self.log(format!("Deleted node {:?}", path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::tree::tests::app_from_hex;
+    use crossterm::event::KeyModifiers;
+    use std::collections::HashMap;
+
+    fn child_values(obj: &OwnedObject) -> Vec<Vec<u8>> {
+        match &obj.value {
+            OwnedValue::Constructed(children) => children.iter().map(encode_content).collect(),
+            OwnedValue::Primitive(_) => panic!("Expected constructed value"),
+        }
+    }
+
+    #[test]
+    fn test_delete_middle_child() {
+        // SEQUENCE { INTEGER 1, INTEGER 2, INTEGER 3 }
+        let mut app = app_from_hex("3009020101020102020103");
+        app.selected_path = vec![0, 1];
+        app.delete_selected();
+
+        assert_eq!(child_values(&app.parsed_objects[0]), vec![vec![1], vec![3]]);
+        assert_eq!(app.parsed_objects[0].length, 6);
+        assert_eq!(app.selected_path, vec![0, 0]);
+    }
+
+    #[test]
+    fn test_delete_first_child_selects_parent() {
+        let mut app = app_from_hex("3006020101020102");
+        app.selected_path = vec![0, 0];
+        app.delete_selected();
+
+        assert_eq!(child_values(&app.parsed_objects[0]), vec![vec![2]]);
+        assert_eq!(app.selected_path, vec![0]);
+    }
+
+    #[test]
+    fn test_delete_remaps_collapsed_nodes() {
+        // SEQUENCE { SEQUENCE { INTEGER 1 }, SEQUENCE { INTEGER 2 }, SEQUENCE { INTEGER 3 } }
+        let mut app = app_from_hex("300F300302010130030201023003020103");
+        app.collapsed_nodes.insert(vec![0, 0]);
+        app.collapsed_nodes.insert(vec![0, 1]);
+        app.collapsed_nodes.insert(vec![0, 2]);
+        app.selected_path = vec![0, 1];
+        app.delete_selected();
+
+        let mut collapsed: Vec<_> = app.collapsed_nodes.iter().cloned().collect();
+        collapsed.sort();
+        assert_eq!(collapsed, vec![vec![0, 0], vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_delete_remaps_decode_overrides() {
+        // SEQUENCE { OCTET STRING 2A, OCTET STRING 2B, OCTET STRING 2C }
+        let mut app = app_from_hex("300904012A04012B04012C");
+        app.decode_overrides.insert(vec![0, 0], 6);
+        app.decode_overrides.insert(vec![0, 2], 2);
+        app.selected_path = vec![0, 1];
+        app.delete_selected();
+        assert_eq!(
+            app.decode_overrides,
+            HashMap::from([(vec![0, 0], 6), (vec![0, 1], 2)])
+        );
+        app.selected_path = vec![0, 1];
+        app.delete_selected();
+        assert_eq!(app.decode_overrides, HashMap::from([(vec![0, 0], 6)]));
+    }
+
+    #[test]
+    fn test_add_child_to_sequence() {
+        let mut app = app_from_hex("3006020101020102");
+        app.collapsed_nodes.insert(vec![0]);
+        app.add_child();
+
+        let OwnedValue::Constructed(children) = &app.parsed_objects[0].value else {
+            panic!("Expected constructed value");
+        };
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[2].tag.number, 5);
+        assert_eq!(app.parsed_objects[0].length, 8);
+        assert_eq!(app.selected_path, vec![0, 2]);
+        assert!(app.collapsed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_add_sibling_after_primitive() {
+        // SEQUENCE { INTEGER 1, SEQUENCE { INTEGER 2 } }
+        let mut app = app_from_hex("30080201013003020102");
+        app.collapsed_nodes.insert(vec![0, 1]);
+        app.pinned_path = Some(vec![0, 1, 0]);
+        app.selected_path = vec![0, 0];
+        app.add_child();
+
+        assert_eq!(app.selected_path, vec![0, 1]);
+        assert_eq!(app.get_selected_object().unwrap().tag.number, 5);
+        assert!(app.collapsed_nodes.contains(&vec![0, 2]));
+        assert_eq!(app.pinned_path, Some(vec![0, 2, 0]));
+    }
+
+    #[test]
+    fn test_reparse_bit_string_wrapping_sequence() {
+        // SEQUENCE { BIT STRING (0 unused bits) wrapping SEQUENCE { INTEGER 5 } }
+        let mut app = app_from_hex("30080306003003020105");
+        app.selected_path = vec![0, 0];
+        app.reparse_selected();
+
+        let bit_string = app.get_selected_object().unwrap();
+        assert!(!bit_string.tag.constructed);
+        assert_eq!(
+            bit_string.value,
+            OwnedValue::Primitive(hex::decode("003003020105").unwrap())
+        );
+        let children = bit_string.wrapped.as_ref().unwrap();
+        assert_eq!(children[0].tag.number, 16);
+        assert_eq!(child_values(&children[0]), vec![vec![5]]);
+        // Offsets point into the original buffer.
+        assert_eq!((children[0].tlv_start, children[0].tlv_end), (5, 10));
+        // The wrapped objects get rows of their own below the BIT STRING.
+        let (items, _) = crate::tui::tree::tui_list_items(
+            &app.parsed_objects,
+            app.tree_view(),
+            app.display_options(),
+        );
+        assert_eq!(items.len(), 4);
+        // The wrapped form re-encodes to the original bytes.
+        assert_eq!(
+            crate::der_encoder::to_der(&app.parsed_objects[0]),
+            hex::decode("30080306003003020105").unwrap()
+        );
+        assert!(app.last_error.is_none());
+    }
+
+    #[test]
+    fn test_edit_below_reparsed_bit_string_rewrites_its_bytes() {
+        // SEQUENCE { BIT STRING (0 unused bits) wrapping SEQUENCE { INTEGER 5 } }
+        let mut app = app_from_hex("30080306003003020105");
+        app.selected_path = vec![0, 0];
+        app.reparse_selected();
+        app.selected_path = vec![0, 0, 0, 0];
+        assert!(app.set_selected_value("300"));
+
+        let bit_string = &app.parsed_objects[0].children().unwrap()[0];
+        assert_eq!(
+            bit_string.value,
+            OwnedValue::Primitive(hex::decode("0030040202012C").unwrap())
+        );
+        assert_eq!(bit_string.length, 7);
+        assert_eq!(
+            crate::der_encoder::to_der(&app.parsed_objects[0]),
+            hex::decode("300903070030040202012C").unwrap()
+        );
+
+        // Editing the BIT STRING itself drops the objects it wrapped.
+        app.selected_path = vec![0, 0];
+        assert!(app.set_selected_value("00FF"));
+        assert_eq!(app.get_selected_object().unwrap().wrapped, None);
+    }
+
+    #[test]
+    fn test_reparse_failure_leaves_node_untouched() {
+        // OCTET STRING { 01 02 }
+        let mut app = app_from_hex("04020102");
+        app.reparse_selected();
+
+        assert_eq!(
+            app.parsed_objects[0].value,
+            OwnedValue::Primitive(vec![1, 2])
+        );
+        assert!(
+            app.last_error
+                .as_deref()
+                .unwrap()
+                .starts_with("Node [0] does not wrap DER: ")
+        );
+    }
+
+    #[test]
+    fn test_edit_string_value() {
+        // SEQUENCE { UTF8String "hi", INTEGER 1 }
+        let mut app = app_from_hex("30070C026869020101");
+        app.mode = crate::tui::app::AppMode::View;
+        app.selected_path = vec![0, 0];
+        app.handle_input(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert!(app.edit_active);
+        assert_eq!(app.edit_buffer, "hi");
+        for c in "!é".chars() {
+            app.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(!app.edit_active);
+        let edited = app.get_selected_object().unwrap();
+        assert_eq!(
+            edited.value,
+            OwnedValue::Primitive("hi!é".as_bytes().to_vec())
+        );
+        assert_eq!(edited.length, 5);
+        assert_eq!(app.parsed_objects[0].length, 10);
+    }
+
+    #[test]
+    fn test_edit_integer_is_minimally_encoded() {
+        let mut app = app_from_hex("3003020105");
+        app.selected_path = vec![0, 0];
+        app.start_value_edit();
+        assert_eq!(app.edit_buffer, "5");
+
+        assert!(app.set_selected_value("128"));
+        assert_eq!(child_values(&app.parsed_objects[0]), vec![vec![0x00, 0x80]]);
+        assert!(app.set_selected_value("-129"));
+        assert_eq!(child_values(&app.parsed_objects[0]), vec![vec![0xFF, 0x7F]]);
+        assert_eq!(app.get_selected_object().unwrap().length, 2);
+        assert_eq!(app.parsed_objects[0].length, 4);
+
+        assert!(!app.set_selected_value("12a"));
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Invalid value for [0, 0]: \"12a\" is not a decimal integer")
+        );
+        assert_eq!(child_values(&app.parsed_objects[0]), vec![vec![0xFF, 0x7F]]);
+    }
+
+    #[test]
+    fn test_edit_oid_checks_its_arcs() {
+        // OID 1.2.840
+        let mut app = app_from_hex("06032A8648");
+        app.start_value_edit();
+        assert_eq!(app.edit_buffer, "1.2.840");
+
+        assert!(app.set_selected_value("2.5.4.3"));
+        assert_eq!(
+            app.parsed_objects[0].value,
+            OwnedValue::Primitive(vec![0x55, 0x04, 0x03])
+        );
+        assert!(!app.set_selected_value("1.40.1"));
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Invalid value for [0]: invalid second arc in 1.40.1")
+        );
+        assert_eq!(
+            app.parsed_objects[0].value,
+            OwnedValue::Primitive(vec![0x55, 0x04, 0x03])
+        );
+    }
+
+    #[test]
+    fn test_edit_rejects_invalid_printable_string() {
+        let mut app = app_from_hex("13024142");
+        assert!(!app.set_selected_value("a@b"));
+        assert_eq!(
+            app.parsed_objects[0].value,
+            OwnedValue::Primitive(b"AB".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_delete_last_top_level_object() {
+        let mut app = app_from_hex("020101");
+        app.delete_selected();
+
+        assert!(app.parsed_objects.is_empty());
+        assert!(app.selected_path.is_empty());
+        app.delete_selected();
+    }
+}
@@ -1,85 +1,648 @@
 // src/tui/events.rs
-use crate::der_parser::try_decode_input;
-use crate::tui::app::{App, AppMode};
+use crate::der_parser::{DerParser, OwnedValue, decode_input_blocks};
+use crate::tui::app::{App, AppMode, ClipboardFormat};
+use crate::tui::tree::{collapsed_below_depth, same_shape};
+use crate::tui::ui::HEX_ROW_BYTES;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashSet;
+
+/// Rows moved by PageUp/PageDown in the hex modal.
+const HEX_PAGE_ROWS: usize = 10;
 
 impl App {
+    /// Decodes the typed input into `self.buffer` and parses it, reporting a
+    /// decode failure in the Input panel's title. Returns whether anything
+    /// was loaded; if not, `self.buffer` still holds the bytes of the tree
+    /// on screen.
+    pub fn parse_input(&mut self) -> bool {
+        match decode_input_blocks(&self.input_buffer) {
+            Ok(blocks) => {
+                let block_starts: Vec<usize> = blocks
+                    .iter()
+                    .scan(0, |offset, block| {
+                        let start = *offset;
+                        *offset += block.len();
+                        Some(start)
+                    })
+                    .collect();
+                let previous = std::mem::replace(&mut self.buffer, blocks.concat());
+                let loaded = self.parse_buffer_with_blocks(&block_starts);
+                if !loaded {
+                    self.buffer = previous;
+                }
+                loaded
+            }
+            Err(e) => {
+                self.report_error(format!("Input decoding failed: {}", e));
+                self.input_error = Some(e);
+                false
+            }
+        }
+    }
+
+    /// Parses `self.buffer` into the tree, logging the outcome. Bytes after
+    /// the first object are reported; if they don't parse, the first object
+    /// is kept on its own. Returns whether anything was loaded.
+    pub fn parse_buffer(&mut self) -> bool {
+        self.parse_buffer_with_blocks(&[])
+    }
+
+    /// [`App::parse_buffer`] for a buffer decoded from several PEM blocks,
+    /// which begin at `block_starts`. Objects starting a block are expected
+    /// and not reported; only bytes trailing an object within its block are.
+    fn parse_buffer_with_blocks(&mut self, block_starts: &[usize]) -> bool {
+        if self.buffer.is_empty() {
+            self.report_error("Parse failed: input is empty".to_string());
+            return false;
+        }
+        let parsed = DerParser::new(&self.buffer).parse_all_lenient_owned();
+        let warning = match (&parsed.error, parsed.objects.last()) {
+            (Some(e), None) => {
+                self.report_error(format!("Parse failed: {}", e));
+                return false;
+            }
+            (Some(e), Some(last)) => Some(format!(
+                "Warning: ignored {} trailing byte(s) after {} object(s) ({})",
+                self.buffer.len() - last.tlv_end,
+                parsed.objects.len(),
+                e
+            )),
+            (None, _) => {
+                let trailing: Vec<_> = parsed
+                    .objects
+                    .iter()
+                    .skip(1)
+                    .filter(|obj| !block_starts.contains(&obj.tlv_start))
+                    .collect();
+                (!trailing.is_empty()).then(|| {
+                    format!(
+                        "Warning: {} byte(s) follow the first object ({} more object(s))",
+                        trailing
+                            .iter()
+                            .map(|obj| obj.tlv_end - obj.tlv_start)
+                            .sum::<usize>(),
+                        trailing.len()
+                    )
+                })
+            }
+        };
+        self.parse_stop = parsed.error;
+        let objects = parsed.objects;
+        // Re-parsing the same structure (e.g. after editing a value) keeps the
+        // view as the user left it; anything else starts from the defaults.
+        if !same_shape(&self.parsed_objects, &objects) {
+            self.collapsed_nodes = match self.fold_depth {
+                Some(depth) => collapsed_below_depth(&objects, depth),
+                None => HashSet::new(),
+            };
+            self.decode_overrides.clear();
+            self.selected_path = vec![0];
+        }
+        self.parsed_objects = objects;
+        self.clamp_selection();
+        self.last_error = None;
+        self.log(format!(
+            "Parsed {} top-level object(s) from {} bytes",
+            self.parsed_objects.len(),
+            self.buffer.len()
+        ));
+        if let Some(warning) = warning {
+            self.log(warning);
+        }
+        true
+    }
+
+    /// Scrolls the hex modal by `rows`, stopping at its first and last row.
+    /// Rows are counted over the bytes the modal shows, which are the source
+    /// bytes rather than the (possibly shorter) re-encoding.
+    fn scroll_hex(&mut self, rows: usize, down: bool) {
+        let total_rows = self
+            .get_selected_object()
+            .map(|obj| self.selected_tlv_bytes(obj).len().div_ceil(HEX_ROW_BYTES))
+            .unwrap_or(0);
+        self.hex_scroll = if down {
+            (self.hex_scroll + rows).min(total_rows.saturating_sub(1))
+        } else {
+            self.hex_scroll.saturating_sub(rows)
+        };
+    }
+
     pub fn handle_input(&mut self, key: KeyEvent) {
+        self.needs_redraw = true;
         if self.show_help {
             self.show_help = false;
             return;
         }
-        if self.show_hex_modal {
-            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-                self.copy_hex_to_clipboard = true;
-                return;
-            } else {
-                self.copy_hex_to_clipboard = false;
+        self.copied_tree = false;
+        if self.show_hex_modal && key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('c') => {
+                    self.copy_selected_tlv(ClipboardFormat::Hex);
+                    return;
+                }
+                KeyCode::Char('b') => {
+                    self.copy_selected_tlv(ClipboardFormat::Base64);
+                    return;
+                }
+                _ => {}
             }
-        } else {
-            self.copy_hex_to_clipboard = false;
+        }
+        if self.search_active {
+            self.handle_search_key(key);
+            return;
+        }
+        if self.export_active {
+            self.handle_export_key(key);
+            return;
+        }
+        if self.offset_active {
+            self.handle_offset_key(key);
+            return;
+        }
+        if self.edit_active {
+            self.handle_edit_key(key);
+            return;
+        }
+        if self.decode_as_active {
+            self.handle_decode_as_key(key);
+            return;
+        }
+        if self.session_active {
+            self.handle_session_key(key);
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('s') => {
+                    self.start_session_prompt(true);
+                    return;
+                }
+                KeyCode::Char('o') => {
+                    self.start_session_prompt(false);
+                    return;
+                }
+                _ => {}
+            }
+        }
+        if self.mode == AppMode::Input {
+            // Any edit or retry supersedes the previous decode error.
+            self.input_error = None;
+        }
+        if key.code != KeyCode::Char('g') {
+            self.pending_key = None;
         }
         match self.mode {
             AppMode::Input => match key.code {
                 KeyCode::Char('?') => self.show_help = true,
                 KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     self.input_buffer.clear();
+                    self.cursor = 0;
                 }
                 KeyCode::Esc => self.mode = AppMode::View,
                 KeyCode::Tab => self.mode = AppMode::View,
                 KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    // Parse input buffer and update app state
-                    eprintln!("Ctrl-R pressed: parsing input");
-                    eprintln!("Raw input buffer: {}", self.input_buffer);
-                    if let Ok(decoded) = try_decode_input(&self.input_buffer) {
-                        self.buffer = decoded;
-                        let mut parser = crate::der_parser::DerParser::new(&self.buffer);
-                        match parser.parse_all() {
-                            Ok(borrowed_objs) => {
-                                self.parsed_objects = borrowed_objs
-                                    .iter()
-                                    .map(crate::der_parser::OwnedObject::from)
-                                    .collect();
-                                self.selected_path = vec![0];
-                                self.mode = AppMode::View;
-                            }
-                            Err(e) => eprintln!("Parse failed: {:?}", e),
-                        }
-                    } else {
-                        eprintln!("Input decoding failed.");
+                    // A failed parse stays in Input mode to fix the data.
+                    let parsed = self.parse_input();
+                    if parsed {
+                        self.mode = AppMode::View;
                     }
                 }
-                KeyCode::Backspace => {
-                    self.input_buffer.pop();
-                }
-                KeyCode::Enter => {
-                    self.input_buffer.push('\n');
+                // Refreshes the preview below the input without leaving it.
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.parse_input();
                 }
-                KeyCode::Char(c) => self.input_buffer.push(c),
+                KeyCode::Backspace => self.delete_input_backward(),
+                KeyCode::Delete => self.delete_input_forward(),
+                KeyCode::Left => self.move_cursor_left(),
+                KeyCode::Right => self.move_cursor_right(),
+                KeyCode::Home => self.move_cursor_home(),
+                KeyCode::End => self.move_cursor_end(),
+                KeyCode::Enter => self.insert_input_char('\n'),
+                KeyCode::Char(c) => self.insert_input_char(c),
                 _ => {}
             },
             AppMode::View => match key.code {
+                KeyCode::Char('g') if self.pending_key.take() == Some('g') => self.select_first(),
+                KeyCode::Char('g') => self.pending_key = Some('g'),
+                KeyCode::Char('G') => self.select_last(),
+                KeyCode::Char('[') => self.switch_file(false),
+                KeyCode::Char(']') => self.switch_file(true),
                 KeyCode::Char('q') => self.should_quit = true,
                 KeyCode::Char('i') => self.mode = AppMode::Input,
                 KeyCode::Tab => self.mode = AppMode::Input,
-                KeyCode::Char('h') => self.toggle_collapse(),
-                KeyCode::Char('l') => self.toggle_collapse(),
-                KeyCode::Char('j') => {
-                    // Tree area height is the middle chunk (see draw)
-                    let area_height = 10; // Default/fallback
-                    self.move_selection_down(area_height);
-                }
-                KeyCode::Char('k') => {
-                    let area_height = 10;
-                    self.move_selection_up(area_height);
-                }
-                KeyCode::Char('d') => {}
-                KeyCode::Char('a') => {}
-                KeyCode::Char('x') => self.show_hex_modal = true,
-                KeyCode::Esc => self.show_hex_modal = false,
+                KeyCode::Char('h') | KeyCode::Left => self.collapse_or_ascend(),
+                KeyCode::Char('l') | KeyCode::Right => self.expand_or_descend(),
+                KeyCode::Char('j') if self.show_hex_modal => self.scroll_hex(1, true),
+                KeyCode::Char('k') if self.show_hex_modal => self.scroll_hex(1, false),
+                KeyCode::PageDown if self.show_hex_modal => {
+                    self.scroll_hex(HEX_PAGE_ROWS, true);
+                }
+                KeyCode::PageUp if self.show_hex_modal => {
+                    self.scroll_hex(HEX_PAGE_ROWS, false);
+                }
+                KeyCode::Char('j') => self.move_selection_down(self.tree_height),
+                KeyCode::Char('k') => self.move_selection_up(self.tree_height),
+                KeyCode::PageDown => self.page_tree(true),
+                KeyCode::PageUp => self.page_tree(false),
+                KeyCode::Char('d') => self.delete_selected(),
+                KeyCode::Char('a') => self.add_child(),
+                KeyCode::Char('E') | KeyCode::Enter => self.reparse_selected(),
+                KeyCode::Char('x') => {
+                    self.show_hex_modal = true;
+                    self.hex_scroll = 0;
+                }
+                KeyCode::Char('X') => self.show_buffer_hex = !self.show_buffer_hex,
+                KeyCode::Char('t') => self.show_dn_labels = !self.show_dn_labels,
+                KeyCode::Char('p') => self.pretty = !self.pretty,
+                KeyCode::Char('I') => self.inline_single_child = !self.inline_single_child,
+                KeyCode::Char('Y') => self.copy_tree(),
+                KeyCode::Char('-') => self.change_fold_depth(false),
+                KeyCode::Char('+') => self.change_fold_depth(true),
+                KeyCode::Char('m') => self.toggle_pin(),
+                KeyCode::Char('/') => {
+                    self.search_active = true;
+                    self.search_buffer.clear();
+                }
+                KeyCode::Char(c @ ('w' | 'W')) => self.start_export_prompt(c == 'W'),
+                KeyCode::Char('o' | ':') => {
+                    self.offset_active = true;
+                    self.offset_buffer.clear();
+                }
+                // `e` edits a primitive; anywhere else it exports, as before
+                // editing existed.
+                KeyCode::Char('e') => {
+                    let primitive = self
+                        .get_selected_object()
+                        .is_some_and(|obj| matches!(obj.value, OwnedValue::Primitive(_)));
+                    if primitive {
+                        self.start_value_edit();
+                    } else {
+                        self.start_export_prompt(false);
+                    }
+                }
+                KeyCode::Char('D') => self.start_decode_as(),
+                KeyCode::Char('n') => self.search_next(true),
+                KeyCode::Char('N') => self.search_next(false),
+                KeyCode::Char('L') => self.show_log = !self.show_log,
+                KeyCode::Char('K')
+                    if self.show_log && self.log_scroll + 1 < self.messages.len() =>
+                {
+                    self.log_scroll += 1;
+                }
+                KeyCode::Char('J') if self.show_log => {
+                    self.log_scroll = self.log_scroll.saturating_sub(1);
+                }
+                KeyCode::Char('c') => {
+                    self.show_compare_modal =
+                        !self.show_compare_modal && self.pinned_path.is_some();
+                }
+                KeyCode::Char('v') => self.show_validation_modal = !self.show_validation_modal,
+                KeyCode::Esc => {
+                    self.show_hex_modal = false;
+                    self.show_compare_modal = false;
+                    self.show_validation_modal = false;
+                }
                 KeyCode::Char('?') => self.show_help = true,
                 _ => {}
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_input_marks_redraw() {
+        let mut app = App::new();
+        app.needs_redraw = false;
+        app.handle_input(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert!(app.needs_redraw);
+        assert_eq!(app.input_buffer, "a");
+    }
+
+    #[test]
+    fn test_parse_results_are_logged() {
+        let mut app = App::new();
+        app.input_buffer = "zz".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.messages.len(), 1);
+        assert_eq!(
+            app.messages[0].text,
+            "Input decoding failed: input is neither hex nor valid base64"
+        );
+
+        app.input_buffer = "3003020105".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.messages.len(), 2);
+        assert_eq!(
+            app.messages[1].text,
+            "Parsed 1 top-level object(s) from 5 bytes"
+        );
+    }
+
+    #[test]
+    fn test_preview_parse_stays_in_input_mode() {
+        let mut app = App::new();
+        app.input_buffer = "3003020105".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.mode, AppMode::Input);
+        assert_eq!(app.input_buffer, "3003020105");
+        assert_eq!(app.parsed_objects.len(), 1);
+        assert_eq!(app.parsed_objects[0].tag.number, 16);
+
+        // A failed preview keeps the last good tree and its bytes, and shows
+        // the error.
+        app.input_buffer = "3005020105".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.mode, AppMode::Input);
+        assert!(
+            app.last_error
+                .as_deref()
+                .unwrap()
+                .starts_with("Parse failed")
+        );
+        assert_eq!(app.parsed_objects[0].tag.number, 16);
+        assert_eq!(app.buffer, hex::decode("3003020105").unwrap());
+
+        app.input_buffer = "3003020105".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.last_error, None);
+    }
+
+    #[test]
+    fn test_trailing_bytes_are_reported() {
+        let mut app = App::new();
+        app.input_buffer = "300302010100".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.parsed_objects.len(), 1);
+        assert_eq!(app.mode, AppMode::View);
+        assert_eq!(
+            app.messages[1].text,
+            "Warning: ignored 1 trailing byte(s) after 1 object(s) (invalid length at offset 6)"
+        );
+
+        let mut app = App::new();
+        app.input_buffer = "0201010500".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.parsed_objects.len(), 2);
+        assert_eq!(
+            app.messages[1].text,
+            "Warning: 2 byte(s) follow the first object (1 more object(s))"
+        );
+
+        // Each block of a PEM chain is its own object, so only the NULL
+        // inside the second block is extra.
+        let mut app = App::new();
+        app.input_buffer = concat!(
+            "-----BEGIN A-----\nBQA=\n-----END A-----\n",
+            "-----BEGIN B-----\nAgEBBQA=\n-----END B-----\n",
+            "-----BEGIN C-----\nBQA=\n-----END C-----\n",
+        )
+        .to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.parsed_objects.len(), 4);
+        assert_eq!(app.messages.len(), 2);
+        assert_eq!(
+            app.messages[1].text,
+            "Warning: 2 byte(s) follow the first object (1 more object(s))"
+        );
+        let mut app = App::new();
+        app.input_buffer = concat!(
+            "-----BEGIN A-----\nBQA=\n-----END A-----\n",
+            "-----BEGIN B-----\nAgEB\n-----END B-----\n",
+        )
+        .to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.parsed_objects.len(), 2);
+        assert_eq!(app.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_truncated_input_keeps_partial_results() {
+        let mut app = App::new();
+        // SEQUENCE { INTEGER 5 }, INTEGER 1, then a truncated INTEGER.
+        app.input_buffer = "30030201050201010202".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.mode, AppMode::View);
+        assert_eq!(app.parsed_objects.len(), 2);
+        assert_eq!(
+            app.parse_stop,
+            Some(crate::der_parser::ASN1Error::UnexpectedEOF { offset: 9 })
+        );
+        assert_eq!(
+            app.messages[1].text,
+            "Warning: ignored 2 trailing byte(s) after 2 object(s) (unexpected end of input at offset 9)"
+        );
+
+        app.mode = AppMode::Input;
+        app.input_buffer = "3003020105".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.parse_stop, None);
+    }
+
+    #[test]
+    fn test_hex_modal_scrolling() {
+        // OCTET STRING with 40 value bytes: 42 bytes, 3 rows.
+        let mut app = crate::tui::tree::tests::app_from_hex(&format!("0428{}", "AB".repeat(40)));
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        app.handle_input(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(app.hex_scroll, 1);
+        assert_eq!(app.selected_path, vec![0]);
+        app.handle_input(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(app.hex_scroll, 2);
+        app.handle_input(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
+        assert_eq!(app.hex_scroll, 0);
+
+        app.hex_scroll = 2;
+        app.handle_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        app.handle_input(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(app.hex_scroll, 0);
+    }
+
+    #[test]
+    fn test_hex_modal_scrolls_over_source_bytes() {
+        // OCTET STRING of 62 bytes with a non-minimal three-octet length: 66
+        // source bytes (5 rows), though its re-encoding is only 64 (4 rows).
+        let mut app =
+            crate::tui::tree::tests::app_from_hex(&format!("0482003E{}", "AB".repeat(62)));
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        app.handle_input(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(app.hex_scroll, 4);
+    }
+
+    #[test]
+    fn test_decode_error_shown_until_next_edit() {
+        let mut app = App::new();
+        app.input_buffer = "30 03 02 01 0".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(
+            app.input_error,
+            Some(crate::der_parser::DecodeError::NotHex { position: 12 })
+        );
+        assert_eq!(app.mode, AppMode::Input);
+        app.handle_input(KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE));
+        assert_eq!(app.input_error, None);
+    }
+
+    #[test]
+    fn test_parse_failure_sets_last_error() {
+        let mut app = App::new();
+        app.input_buffer = "3005020101".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Parse failed: unexpected end of input at offset 1")
+        );
+        assert_eq!(app.mode, AppMode::Input);
+
+        app.input_buffer = "3003020101".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(app.last_error, None);
+    }
+
+    #[test]
+    fn test_reparse_with_fewer_objects_keeps_a_valid_selection() {
+        let reparse = |app: &mut App, hex: &str| {
+            app.mode = AppMode::Input;
+            app.input_buffer = hex.to_string();
+            app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        };
+        // INTEGER 1, INTEGER 2, INTEGER 3
+        let mut app = App::new();
+        reparse(&mut app, "020101020102020103");
+        app.selected_path = vec![2];
+        reparse(&mut app, "020101");
+        assert_eq!(app.selected_path, vec![0]);
+        assert_eq!(app.get_selected_object().unwrap().tag.number, 2);
+
+        // Deleting the only object leaves nothing selected.
+        app.delete_selected();
+        assert!(app.parsed_objects.is_empty());
+        assert!(app.selected_path.is_empty());
+        assert!(app.get_selected_object().is_none());
+    }
+
+    #[test]
+    fn test_empty_input_is_an_error() {
+        let mut app = App::new();
+        app.input_buffer = " \n\t".to_string();
+        app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Input decoding failed: input is empty")
+        );
+        assert_eq!(app.mode, AppMode::Input);
+        assert!(app.parsed_objects.is_empty());
+
+        // An empty file decodes to no bytes at all.
+        assert!(!app.load_buffer(Vec::new()));
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Parse failed: input is empty")
+        );
+        assert!(app.parsed_objects.is_empty());
+        assert_eq!(app.mode, AppMode::Input);
+    }
+
+    #[test]
+    fn test_reparse_keeps_view_state_only_for_same_structure() {
+        let reparse = |app: &mut App, hex: &str| {
+            app.input_buffer = hex.to_string();
+            app.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        };
+        // SEQUENCE { SEQUENCE { INTEGER 1 }, INTEGER 2 }
+        let mut app = App::new();
+        reparse(&mut app, "30083003020101020102");
+        app.collapsed_nodes.insert(vec![0, 0]);
+        app.selected_path = vec![0, 1];
+
+        // Same structure, different INTEGER values.
+        app.mode = AppMode::Input;
+        reparse(&mut app, "3008300302010502010A");
+        assert!(app.collapsed_nodes.contains(&vec![0, 0]));
+        assert_eq!(app.selected_path, vec![0, 1]);
+
+        // The inner SEQUENCE became a SET: the old paths no longer apply.
+        app.mode = AppMode::Input;
+        reparse(&mut app, "3008310302010502010A");
+        assert!(app.collapsed_nodes.is_empty());
+        assert_eq!(app.selected_path, vec![0]);
+
+        // With a default depth, the new tree is collapsed below the top level.
+        app.fold_depth = Some(1);
+        app.mode = AppMode::Input;
+        reparse(&mut app, "3008300302010502010A");
+        assert_eq!(app.collapsed_nodes, HashSet::from([vec![0, 0]]));
+    }
+
+    #[test]
+    fn test_paging_uses_tree_height() {
+        // SEQUENCE of 30 NULLs: 31 rows.
+        let mut app = crate::tui::tree::tests::app_from_hex(&format!("303C{}", "0500".repeat(30)));
+        app.mode = AppMode::View;
+        app.tree_height = 5;
+        app.handle_input(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(app.selected_path, vec![0, 4]);
+        assert_eq!(app.tree_scroll, 5);
+        app.handle_input(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(app.tree_scroll, 10);
+        app.handle_input(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
+        assert_eq!(app.selected_path, vec![0, 4]);
+        assert_eq!(app.tree_scroll, 5);
+
+        // j scrolls once the selection passes the bottom of the real height.
+        app.handle_input(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
+        assert_eq!(app.tree_scroll, 0);
+        for _ in 0..5 {
+            app.handle_input(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        }
+        assert_eq!(app.tree_scroll, 1);
+    }
+
+    #[test]
+    fn test_jump_to_first_and_last() {
+        // SEQUENCE { SEQUENCE { NULL, NULL } }, SEQUENCE { INTEGER 1, SEQUENCE { NULL } }
+        let mut app = crate::tui::tree::tests::app_from_hex("3006300405000500300702010130020500");
+        app.mode = AppMode::View;
+        let press = |app: &mut App, c: char| {
+            app.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        };
+        press(&mut app, 'G');
+        assert_eq!(app.selected_path, vec![1, 1, 0]);
+        app.collapsed_nodes.insert(vec![1, 1]);
+        press(&mut app, 'G');
+        assert_eq!(app.selected_path, vec![1, 1]);
+
+        // A key between the two g's cancels the sequence.
+        press(&mut app, 'g');
+        press(&mut app, 'n');
+        press(&mut app, 'g');
+        assert_eq!(app.selected_path, vec![1, 1]);
+        press(&mut app, 'g');
+        assert_eq!(app.selected_path, vec![0]);
+        assert_eq!(app.pending_key, None);
+    }
+
+    #[test]
+    fn test_validation_modal_toggle() {
+        let mut app = crate::tui::tree::tests::app_from_hex("0202007F");
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert!(app.show_validation_modal);
+        app.handle_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.show_validation_modal);
+    }
+
+    #[test]
+    fn test_log_line_format() {
+        let message = crate::tui::app::LogMessage {
+            timestamp: std::time::UNIX_EPOCH + std::time::Duration::from_secs(86_400 + 3_723),
+            text: "hello".to_string(),
+        };
+        assert_eq!(message.display_line(), "[01:02:03] hello");
+    }
+}
@@ -0,0 +1,270 @@
+// src/tui/export.rs
+use crate::der_encoder::{to_der_all, to_pem};
+use crate::tui::app::{App, COPY_CONFIRMATION_TIME, ClipboardFormat};
+use crate::tui::ui::clipboard_text;
+use clipboard::{ClipboardContext, ClipboardProvider};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+
+/// Writes `text` to the system clipboard.
+pub fn system_clipboard(text: String) -> Result<(), Box<dyn std::error::Error>> {
+    ClipboardContext::new()?.set_contents(text)
+}
+
+impl App {
+    /// Opens the path prompt for a PEM (`w`, or `e` off a primitive) or DER
+    /// (`W`) export.
+    pub fn start_export_prompt(&mut self, der: bool) {
+        self.export_active = true;
+        self.export_der = der;
+        self.export_buffer.clear();
+    }
+
+    /// Handles a key while the `w`/`W` export prompt is open.
+    pub(crate) fn handle_export_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.export_active = false;
+                let path = std::mem::take(&mut self.export_buffer);
+                match path.trim() {
+                    "" => {}
+                    path if self.export_der => self.export_der(path),
+                    path => self.export_pem(path),
+                }
+            }
+            KeyCode::Esc => {
+                self.export_active = false;
+                self.export_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.export_buffer.pop();
+            }
+            KeyCode::Char(c) => self.export_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Re-encodes the whole tree and writes it to `path` as PEM, labeled
+    /// with `pem_label`. Failures are reported in the error status line.
+    pub fn export_pem(&mut self, path: &str) {
+        if self.parsed_objects.is_empty() {
+            self.report_error("Export failed: nothing has been parsed".to_string());
+            return;
+        }
+        let der = to_der_all(&self.parsed_objects);
+        match std::fs::write(path, to_pem(&der, &self.pem_label)) {
+            Ok(()) => self.log(format!("Exported {} bytes as PEM to {}", der.len(), path)),
+            Err(e) => self.report_error(format!("Export to {} failed: {}", path, e)),
+        }
+    }
+
+    /// Re-encodes the whole tree and writes the raw DER bytes to `path`, with
+    /// nothing added. Failures are reported in the error status line.
+    pub fn export_der(&mut self, path: &str) {
+        if self.parsed_objects.is_empty() {
+            self.report_error("Export failed: nothing has been parsed".to_string());
+            return;
+        }
+        let der = to_der_all(&self.parsed_objects);
+        match std::fs::write(path, &der) {
+            Ok(()) => self.log(format!("Exported {} bytes as DER to {}", der.len(), path)),
+            Err(e) => self.report_error(format!("Export to {} failed: {}", path, e)),
+        }
+    }
+
+    /// The whole tree in the `--dump` text format, laid out with
+    /// [`App::render_options`].
+    pub fn tree_text(&self) -> String {
+        crate::format::render_tree_string(&self.parsed_objects, &self.render_options)
+    }
+
+    /// Copies [`App::tree_text`] to the clipboard (`Y`). Success sets
+    /// `copied_tree` for a confirmation; failures go to the error status line.
+    pub fn copy_tree(&mut self) {
+        if self.parsed_objects.is_empty() {
+            self.report_error("Copy failed: nothing has been parsed".to_string());
+            return;
+        }
+        let text = self.tree_text();
+        match (self.clipboard)(text) {
+            Ok(()) => self.copied_tree = true,
+            Err(e) => self.report_error(format!("Copy failed: {}", e)),
+        }
+    }
+
+    /// Copies the selected node's TLV bytes from the hex modal (Ctrl-C as
+    /// hex, Ctrl-B as base64) and starts the modal's confirmation.
+    pub fn copy_selected_tlv(&mut self, format: ClipboardFormat) {
+        let Some(obj) = self.get_selected_object() else {
+            return;
+        };
+        let text = clipboard_text(&self.selected_tlv_bytes(obj), format);
+        match (self.clipboard)(text) {
+            Ok(()) => self.copy_confirmation = Some(Instant::now()),
+            Err(e) => self.report_error(format!("Copy failed: {}", e)),
+        }
+    }
+
+    /// Drops an expired copy confirmation, asking for a redraw to clear it.
+    /// Called on every turn of the event loop, including idle ones.
+    pub fn expire_copy_confirmation(&mut self) {
+        if self
+            .copy_confirmation
+            .is_some_and(|at| at.elapsed() >= COPY_CONFIRMATION_TIME)
+        {
+            self.copy_confirmation = None;
+            self.needs_redraw = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::der_encoder::to_der_all;
+    use crate::der_parser::parse_owned;
+    use crate::tui::app::AppMode;
+    use crate::tui::tree::tests::app_from_hex;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn test_export_prompt_writes_pem() {
+        let path =
+            std::env::temp_dir().join(format!("asn1smith-export-{}.pem", std::process::id()));
+        let mut app = app_from_hex("3003020105");
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE));
+        assert!(app.export_active);
+        for c in path.to_str().unwrap().chars() {
+            app.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.export_active);
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            written,
+            "-----BEGIN CERTIFICATE-----\nMAMCAQU=\n-----END CERTIFICATE-----\n"
+        );
+    }
+
+    #[test]
+    fn test_e_exports_unless_a_primitive_is_selected() {
+        let mut app = app_from_hex("3003020105");
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert!(app.export_active && !app.export_der);
+        assert!(!app.edit_active);
+
+        app.handle_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        app.selected_path = vec![0, 0];
+        app.handle_input(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert!(app.edit_active);
+        assert!(!app.export_active);
+    }
+
+    #[test]
+    fn test_der_export_round_trips() {
+        let path =
+            std::env::temp_dir().join(format!("asn1smith-export-{}.der", std::process::id()));
+        let mut app = app_from_hex("30080201050C03616263");
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('W'), KeyModifiers::NONE));
+        assert!(app.export_active && app.export_der);
+        for c in path.to_str().unwrap().chars() {
+            app.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let written = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, to_der_all(&app.parsed_objects));
+        let reparsed = parse_owned(&written).unwrap();
+        assert!(crate::tui::tree::same_shape(&app.parsed_objects, &reparsed));
+    }
+
+    #[test]
+    fn test_tree_text_is_plain_dump() {
+        // SEQUENCE { INTEGER 5, NULL }
+        let app = app_from_hex("30050201050500");
+        assert_eq!(
+            app.tree_text(),
+            "Tag: class=Universal, constructed=true, number=16 (SEQUENCE)\n\
+             \x20 Constructed: 2 children:\n\
+             \x20 Tag: class=Universal, constructed=false, number=2 (INTEGER)\n\
+             \x20   INTEGER: 5 (1 bytes, 3-bit)\n\
+             \x20 Tag: class=Universal, constructed=false, number=5 (NULL)\n\
+             \x20   NULL: (0 bytes)\n"
+        );
+    }
+
+    thread_local! {
+        static COPIED: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    fn record_copy(text: String) -> Result<(), Box<dyn std::error::Error>> {
+        COPIED.with(|copied| copied.borrow_mut().push(text));
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_modal_copy_happens_in_the_key_handler() {
+        let mut app = app_from_hex("3003020105");
+        app.clipboard = record_copy;
+        app.mode = AppMode::View;
+        app.show_hex_modal = true;
+        app.handle_input(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(app.copy_confirmation.is_some());
+        assert_eq!(
+            COPIED.with(|copied| copied.borrow().clone()),
+            vec!["30 03 02 01 05".to_string()]
+        );
+
+        // Drawing only reflects the confirmation; it doesn't copy again.
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 30)).unwrap();
+        for _ in 0..2 {
+            terminal.draw(|f| app.draw(f)).unwrap();
+        }
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Copied to clipboard!"));
+        assert_eq!(COPIED.with(|copied| copied.borrow().len()), 1);
+
+        // Another key leaves the confirmation to expire on its own.
+        app.handle_input(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(app.copy_confirmation.is_some());
+        app.copy_confirmation =
+            Some(std::time::Instant::now() - crate::tui::app::COPY_CONFIRMATION_TIME);
+        app.needs_redraw = false;
+        app.expire_copy_confirmation();
+        assert!(app.copy_confirmation.is_none());
+        assert!(app.needs_redraw);
+    }
+
+    #[test]
+    fn test_copy_tree_without_objects_reports_error() {
+        let mut app = crate::tui::app::App::new();
+        app.copy_tree();
+        assert!(!app.copied_tree);
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Copy failed: nothing has been parsed")
+        );
+    }
+
+    #[test]
+    fn test_export_write_error_sets_last_error() {
+        let mut app = app_from_hex("0500");
+        app.export_pem("/nonexistent/dir/out.pem");
+        assert!(
+            app.last_error
+                .as_deref()
+                .unwrap()
+                .starts_with("Export to /nonexistent/dir/out.pem failed: ")
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// src/tui/files.rs
+use crate::der_parser::{ASN1Error, OwnedObject};
+use crate::tui::app::App;
+use std::collections::{HashMap, HashSet};
+
+/// A file opened from the command line. While another file is shown, this
+/// holds its tree and how it was being viewed; the active file's entry keeps
+/// only its name, since its state lives in the `App` fields.
+#[derive(Debug, Default)]
+pub struct OpenFile {
+    pub name: String,
+    pub buffer: Vec<u8>,
+    pub parsed_objects: Vec<OwnedObject>,
+    pub parse_stop: Option<ASN1Error>,
+    pub selected_path: Vec<usize>,
+    pub collapsed_nodes: HashSet<Vec<usize>>,
+    pub decode_overrides: HashMap<Vec<usize>, u32>,
+    pub pinned_path: Option<Vec<usize>>,
+    pub tree_scroll: usize,
+}
+
+impl App {
+    /// Exchanges the shown tree and view state with the entry of file `index`.
+    fn swap_file_state(&mut self, index: usize) {
+        let file = &mut self.files[index];
+        std::mem::swap(&mut self.buffer, &mut file.buffer);
+        std::mem::swap(&mut self.parsed_objects, &mut file.parsed_objects);
+        std::mem::swap(&mut self.parse_stop, &mut file.parse_stop);
+        std::mem::swap(&mut self.selected_path, &mut file.selected_path);
+        std::mem::swap(&mut self.collapsed_nodes, &mut file.collapsed_nodes);
+        std::mem::swap(&mut self.decode_overrides, &mut file.decode_overrides);
+        std::mem::swap(&mut self.pinned_path, &mut file.pinned_path);
+        std::mem::swap(&mut self.tree_scroll, &mut file.tree_scroll);
+    }
+
+    /// Adds a file and shows it, parsing `bytes` as [`App::load_buffer`]
+    /// does. Returns whether it parsed; if not, the file is not added and the
+    /// previously shown file stays active.
+    pub fn open_file(&mut self, name: String, bytes: Vec<u8>) -> bool {
+        let previous = self.active_file;
+        if !self.files.is_empty() {
+            self.swap_file_state(previous);
+        }
+        self.files.push(OpenFile {
+            name,
+            ..OpenFile::default()
+        });
+        self.active_file = self.files.len() - 1;
+        if self.load_buffer(bytes) {
+            return true;
+        }
+        self.files.pop();
+        self.active_file = previous;
+        if !self.files.is_empty() {
+            self.swap_file_state(previous);
+            // Leave the entry holding only the name, as for any active file.
+            let name = std::mem::take(&mut self.files[previous].name);
+            self.files[previous] = OpenFile {
+                name,
+                ..OpenFile::default()
+            };
+        }
+        false
+    }
+
+    /// Shows the next (`]`) or previous (`[`) open file, wrapping around.
+    /// Each file keeps its own selection, collapsed nodes and scroll position.
+    pub fn switch_file(&mut self, forward: bool) {
+        let count = self.files.len();
+        if count < 2 {
+            return;
+        }
+        self.swap_file_state(self.active_file);
+        self.active_file = if forward {
+            (self.active_file + 1) % count
+        } else {
+            (self.active_file + count - 1) % count
+        };
+        self.swap_file_state(self.active_file);
+        self.show_hex_modal = false;
+        self.log(format!(
+            "Showing {} ({}/{})",
+            self.files[self.active_file].name,
+            self.active_file + 1,
+            count
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::app::AppMode;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn test_files_keep_independent_trees() {
+        let mut app = App::new();
+        // SEQUENCE { INTEGER 1, NULL } and OCTET STRING 01 02
+        assert!(app.open_file("a.der".to_string(), hex::decode("30050201010500").unwrap()));
+        app.selected_path = vec![0, 1];
+        app.collapsed_nodes.insert(vec![0]);
+        assert!(app.open_file("b.der".to_string(), hex::decode("04020102").unwrap()));
+        assert_eq!(app.files.len(), 2);
+        assert_eq!(app.active_file, 1);
+        assert_eq!(app.parsed_objects[0].tag.number, 4);
+        assert_eq!(app.selected_path, vec![0]);
+        assert!(app.collapsed_nodes.is_empty());
+        assert_eq!(app.tree_title(), "ASN.1 Tree View — b.der (2/2)");
+
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE));
+        assert_eq!(app.active_file, 0);
+        assert_eq!(app.parsed_objects[0].tag.number, 16);
+        assert_eq!(app.buffer, hex::decode("30050201010500").unwrap());
+        assert_eq!(app.selected_path, vec![0, 1]);
+        assert!(app.collapsed_nodes.contains(&vec![0]));
+
+        app.handle_input(KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE));
+        assert_eq!(app.active_file, 1);
+        assert_eq!(app.parsed_objects[0].tag.number, 4);
+    }
+
+    #[test]
+    fn test_unparseable_file_is_not_added() {
+        let mut app = App::new();
+        assert!(app.open_file("a.der".to_string(), vec![0x05, 0x00]));
+        assert!(!app.open_file("bad.der".to_string(), vec![0x30, 0x05]));
+        assert_eq!(app.files.len(), 1);
+        assert_eq!(app.active_file, 0);
+        assert_eq!(app.buffer, vec![0x05, 0x00]);
+        assert_eq!(app.parsed_objects[0].tag.number, 5);
+    }
+}
@@ -0,0 +1,155 @@
+// src/tui/goto.rs
+use crate::der_parser::OwnedObject;
+use crate::tui::app::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Path of the innermost node whose `[tlv_start, tlv_end)` contains
+/// `offset`. Nodes without source offsets (e.g. edited ones) never match,
+/// but their children are still searched.
+pub fn path_at_offset(objects: &[OwnedObject], offset: usize) -> Option<Vec<usize>> {
+    fn walk(
+        objects: &[OwnedObject],
+        offset: usize,
+        path: &mut Vec<usize>,
+        best: &mut Option<Vec<usize>>,
+    ) {
+        for (i, obj) in objects.iter().enumerate() {
+            path.push(i);
+            if (obj.tlv_start..obj.tlv_end).contains(&offset)
+                && best.as_ref().is_none_or(|b| b.len() < path.len())
+            {
+                *best = Some(path.clone());
+            }
+            if let Some(children) = obj.children() {
+                walk(children, offset, path, best);
+            }
+            path.pop();
+        }
+    }
+    let mut best = None;
+    walk(objects, offset, &mut Vec::new(), &mut best);
+    best
+}
+
+/// Parses an offset typed as decimal or as `0x`-prefixed hex.
+fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(digits) => usize::from_str_radix(digits, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+impl App {
+    /// Handles a key while the `o`/`:` jump-to-offset prompt is open.
+    pub(crate) fn handle_offset_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.offset_active = false;
+                let text = std::mem::take(&mut self.offset_buffer);
+                if !text.trim().is_empty() {
+                    self.jump_to_offset(&text);
+                }
+            }
+            KeyCode::Esc => {
+                self.offset_active = false;
+                self.offset_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.offset_buffer.pop();
+            }
+            KeyCode::Char(c) => self.offset_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Selects the innermost node containing the byte offset in `text`,
+    /// expanding its collapsed ancestors. Unparseable or out-of-range offsets
+    /// are reported in the error status line.
+    pub fn jump_to_offset(&mut self, text: &str) {
+        let Some(offset) = parse_offset(text) else {
+            self.report_error(format!("Jump failed: {:?} is not an offset", text.trim()));
+            return;
+        };
+        let Some(target) = path_at_offset(&self.parsed_objects, offset) else {
+            self.report_error(format!(
+                "Jump failed: offset {} is outside the parsed objects",
+                offset
+            ));
+            return;
+        };
+        for depth in 1..target.len() {
+            self.collapsed_nodes.remove(&target[..depth]);
+        }
+        self.selected_path = target;
+        self.update_tree_scroll(self.tree_height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::app::AppMode;
+    use crate::tui::tree::tests::app_from_hex;
+    use crossterm::event::KeyModifiers;
+
+    /// SEQUENCE { INTEGER 5, SEQUENCE { OCTET STRING 01 02 03 } }, NULL
+    const NESTED: &str = "300A02010530050403010203";
+
+    #[test]
+    fn test_path_at_offset_finds_innermost_node() {
+        let app = app_from_hex(&format!("{}0500", NESTED));
+        let objects = &app.parsed_objects;
+        // The middle value byte of the OCTET STRING.
+        assert_eq!(path_at_offset(objects, 10), Some(vec![0, 1, 0]));
+        // The inner SEQUENCE's length byte.
+        assert_eq!(path_at_offset(objects, 6), Some(vec![0, 1]));
+        assert_eq!(path_at_offset(objects, 0), Some(vec![0]));
+        assert_eq!(path_at_offset(objects, 13), Some(vec![1]));
+        assert_eq!(path_at_offset(objects, 14), None);
+    }
+
+    #[test]
+    fn test_offset_prompt_selects_node() {
+        let mut app = app_from_hex(NESTED);
+        app.mode = AppMode::View;
+        app.collapsed_nodes.insert(vec![0, 1]);
+        let mut press = |code| app.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+        press(KeyCode::Char('o'));
+        for c in "0xA".chars() {
+            press(KeyCode::Char(c));
+        }
+        press(KeyCode::Enter);
+        assert_eq!(app.selected_path, vec![0, 1, 0]);
+        assert!(app.collapsed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_offset_prompt_cancel() {
+        let mut app = app_from_hex(NESTED);
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE));
+        assert!(app.offset_active);
+        app.handle_input(KeyEvent::new(KeyCode::Char('7'), KeyModifiers::NONE));
+        app.handle_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.offset_active);
+        assert!(app.offset_buffer.is_empty());
+        assert_eq!(app.selected_path, vec![0]);
+    }
+
+    #[test]
+    fn test_jump_out_of_range_sets_last_error() {
+        let mut app = app_from_hex(NESTED);
+        app.jump_to_offset("99");
+        assert_eq!(app.selected_path, vec![0]);
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Jump failed: offset 99 is outside the parsed objects")
+        );
+        app.jump_to_offset("ten");
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Jump failed: \"ten\" is not an offset")
+        );
+    }
+}
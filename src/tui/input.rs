@@ -0,0 +1,159 @@
+// src/tui/input.rs
+use crate::tui::app::App;
+
+impl App {
+    /// Inserts `c` at the cursor and moves the cursor past it.
+    pub fn insert_input_char(&mut self, c: char) {
+        let at = self.input_cursor();
+        self.input_buffer.insert(at, c);
+        self.cursor = at + c.len_utf8();
+    }
+
+    /// Deletes the character before the cursor (Backspace).
+    pub fn delete_input_backward(&mut self) {
+        let at = self.input_cursor();
+        if let Some(c) = self.input_buffer[..at].chars().next_back() {
+            self.cursor = at - c.len_utf8();
+            self.input_buffer.remove(self.cursor);
+        }
+    }
+
+    /// Deletes the character under the cursor (Delete).
+    pub fn delete_input_forward(&mut self) {
+        let at = self.input_cursor();
+        if at < self.input_buffer.len() {
+            self.input_buffer.remove(at);
+        }
+        self.cursor = at;
+    }
+
+    /// Moves the cursor one character back (Left).
+    pub fn move_cursor_left(&mut self) {
+        let at = self.input_cursor();
+        self.cursor = self.input_buffer[..at]
+            .chars()
+            .next_back()
+            .map_or(at, |c| at - c.len_utf8());
+    }
+
+    /// Moves the cursor one character forward (Right).
+    pub fn move_cursor_right(&mut self) {
+        let at = self.input_cursor();
+        self.cursor = self.input_buffer[at..]
+            .chars()
+            .next()
+            .map_or(at, |c| at + c.len_utf8());
+    }
+
+    /// Moves the cursor to the start of its line (Home).
+    pub fn move_cursor_home(&mut self) {
+        let at = self.input_cursor();
+        self.cursor = self.input_buffer[..at].rfind('\n').map_or(0, |i| i + 1);
+    }
+
+    /// Moves the cursor to the end of its line (End).
+    pub fn move_cursor_end(&mut self) {
+        let at = self.input_cursor();
+        self.cursor = self.input_buffer[at..]
+            .find('\n')
+            .map_or(self.input_buffer.len(), |i| at + i);
+    }
+
+    /// The cursor as a byte offset into `input_buffer`, clamped to the
+    /// buffer and moved back onto a character boundary in case the buffer
+    /// was replaced without updating `cursor`.
+    pub fn input_cursor(&self) -> usize {
+        let mut at = self.cursor.min(self.input_buffer.len());
+        while !self.input_buffer.is_char_boundary(at) {
+            at -= 1;
+        }
+        at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tui::app::App;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_insert_in_middle() {
+        let mut app = App::new();
+        type_text(&mut app, "300305");
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Left);
+        type_text(&mut app, "0201");
+        assert_eq!(app.input_buffer, "3003020105");
+        assert_eq!(app.cursor, 8);
+
+        press(&mut app, KeyCode::Home);
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.input_buffer, "x3003020105");
+        press(&mut app, KeyCode::End);
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(app.input_buffer, "x3003020105y");
+    }
+
+    #[test]
+    fn test_delete_backward_and_forward() {
+        let mut app = App::new();
+        type_text(&mut app, "30x03");
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.input_buffer, "3003");
+        assert_eq!(app.cursor, 2);
+        press(&mut app, KeyCode::Delete);
+        assert_eq!(app.input_buffer, "303");
+        assert_eq!(app.cursor, 2);
+
+        // Nothing to delete at either end.
+        press(&mut app, KeyCode::End);
+        press(&mut app, KeyCode::Delete);
+        press(&mut app, KeyCode::Home);
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.input_buffer, "303");
+    }
+
+    #[test]
+    fn test_home_and_end_stay_on_current_line() {
+        let mut app = App::new();
+        type_text(&mut app, "ab");
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "cé");
+        press(&mut app, KeyCode::Home);
+        assert_eq!(app.cursor, 3);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.cursor, app.input_buffer.len());
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.input_buffer, "ab\né");
+        press(&mut app, KeyCode::Home);
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Home);
+        assert_eq!(app.cursor, 0);
+        press(&mut app, KeyCode::End);
+        assert_eq!(app.cursor, 2);
+    }
+
+    #[test]
+    fn test_cursor_clamped_after_buffer_replaced() {
+        let mut app = App::new();
+        type_text(&mut app, "abcdef");
+        app.input_buffer = "é".to_string();
+        assert_eq!(app.input_cursor(), 2);
+        app.cursor = 1;
+        assert_eq!(app.input_cursor(), 0);
+    }
+}
@@ -1,6 +1,15 @@
 // src/tui/mod.rs
 
 pub mod app;
+pub mod decode_as;
+pub mod edit;
 pub mod events;
+pub mod export;
+pub mod files;
+pub mod goto;
+pub mod input;
+pub mod search;
+pub mod session;
+pub mod terminal;
 pub mod tree;
 pub mod ui;
@@ -0,0 +1,179 @@
+// src/tui/search.rs
+use crate::der_parser::OwnedObject;
+use crate::tui::app::App;
+use crate::tui::tree::{tag_display, value_display};
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Whether a node's tag name or decoded value (which includes OID strings
+/// and their names) contains `query`, ignoring case. UTCTime years are read
+/// with `utc_year_pivot`, as the tree shows them.
+pub fn node_matches(obj: &OwnedObject, query: &str, utc_year_pivot: u32) -> bool {
+    let query = query.to_lowercase();
+    tag_display(obj).to_lowercase().contains(&query)
+        || value_display(obj, utc_year_pivot)
+            .to_lowercase()
+            .contains(&query)
+}
+
+/// Paths of every node matching `query`, in the same pre-order the tree is
+/// flattened in. Collapsed subtrees are searched too.
+pub fn search_matches(
+    objects: &[OwnedObject],
+    query: &str,
+    utc_year_pivot: u32,
+) -> Vec<Vec<usize>> {
+    fn walk(
+        obj: &OwnedObject,
+        path: &mut Vec<usize>,
+        query: &str,
+        pivot: u32,
+        out: &mut Vec<Vec<usize>>,
+    ) {
+        if node_matches(obj, query, pivot) {
+            out.push(path.clone());
+        }
+        if let Some(children) = obj.children() {
+            for (i, child) in children.iter().enumerate() {
+                path.push(i);
+                walk(child, path, query, pivot, out);
+                path.pop();
+            }
+        }
+    }
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    for (i, obj) in objects.iter().enumerate() {
+        walk(obj, &mut vec![i], query, utc_year_pivot, &mut matches);
+    }
+    matches
+}
+
+impl App {
+    /// Handles a key while the `/` search prompt is open.
+    pub(crate) fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.search_active = false;
+                self.search_next(true);
+            }
+            KeyCode::Esc => {
+                self.search_active = false;
+                self.search_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.search_buffer.pop();
+            }
+            KeyCode::Char(c) => self.search_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Moves the selection to the next (or previous) match of `search_buffer`,
+    /// wrapping around and expanding any collapsed ancestors of the match.
+    pub fn search_next(&mut self, forward: bool) {
+        let matches = search_matches(
+            &self.parsed_objects,
+            &self.search_buffer,
+            self.render_options.utc_year_pivot,
+        );
+        // Lexicographic order on paths is the tree's pre-order.
+        let target = if forward {
+            matches
+                .iter()
+                .find(|p| **p > self.selected_path)
+                .or(matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|p| **p < self.selected_path)
+                .or(matches.last())
+        };
+        let Some(target) = target.cloned() else {
+            if !self.search_buffer.is_empty() {
+                self.log(format!("No matches for '{}'", self.search_buffer));
+            }
+            return;
+        };
+        for depth in 1..target.len() {
+            self.collapsed_nodes.remove(&target[..depth]);
+        }
+        self.selected_path = target;
+        self.update_tree_scroll(self.tree_height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::tree::tests::app_from_hex;
+    use crossterm::event::KeyModifiers;
+
+    /// SEQUENCE { OID sha256WithRSAEncryption, NULL, SEQUENCE { INTEGER 5, OID commonName } }
+    fn sample_app() -> App {
+        app_from_hex("301706092A864886F70D01010B050030080201050603550403")
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_search_jumps_into_collapsed_node() {
+        let mut app = sample_app();
+        app.collapsed_nodes.insert(vec![0, 2]);
+        app.search_buffer = "commonname".to_string();
+        app.search_next(true);
+        assert_eq!(app.selected_path, vec![0, 2, 1]);
+        assert!(app.collapsed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_search_cycles_and_wraps() {
+        let mut app = sample_app();
+        app.search_buffer = "OBJECT".to_string();
+        app.search_next(true);
+        assert_eq!(app.selected_path, vec![0, 0]);
+        app.search_next(true);
+        assert_eq!(app.selected_path, vec![0, 2, 1]);
+        app.search_next(true);
+        assert_eq!(app.selected_path, vec![0, 0]);
+        app.search_next(false);
+        assert_eq!(app.selected_path, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_search_prompt_keys() {
+        let mut app = sample_app();
+        app.mode = crate::tui::app::AppMode::View;
+        press(&mut app, KeyCode::Char('/'));
+        assert!(app.search_active);
+        for c in "nulx".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Char('l'));
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.search_active);
+        assert_eq!(app.selected_path, vec![0, 1]);
+
+        // 'n' is a search key again once the prompt is closed.
+        app.search_buffer = "1.2.840".to_string();
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.selected_path, vec![0, 0]);
+    }
+
+    #[test]
+    fn test_search_without_matches_keeps_selection() {
+        let mut app = sample_app();
+        app.search_buffer = "nothing here".to_string();
+        app.search_next(true);
+        assert_eq!(app.selected_path, vec![0]);
+        assert_eq!(
+            app.messages.last().unwrap().text,
+            "No matches for 'nothing here'"
+        );
+    }
+}
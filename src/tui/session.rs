@@ -0,0 +1,311 @@
+// src/tui/session.rs
+use crate::der_parser::{OwnedValue, get_by_path};
+use crate::tui::app::{App, AppMode};
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::Value;
+
+/// What `Ctrl-S` saves and `Ctrl-O` restores: the input text the tree was
+/// parsed from and where the user was in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub input: String,
+    /// Paths of collapsed nodes, sorted so saved files are stable.
+    pub collapsed: Vec<Vec<usize>>,
+    pub selected: Vec<usize>,
+}
+
+impl Session {
+    /// The session as a single line of JSON:
+    /// `{"collapsed":[[0,1],...],"input":"<text>","selected":[0,1]}`.
+    pub fn to_json(&self) -> String {
+        let value = serde_json::json!({
+            "input": self.input,
+            "collapsed": self.collapsed,
+            "selected": self.selected,
+        });
+        format!("{}\n", value)
+    }
+
+    /// Reads a session written by [`Session::to_json`]. Unknown keys are
+    /// ignored; a missing key or a value of the wrong kind is an error.
+    pub fn from_json(text: &str) -> Result<Session, String> {
+        let value: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let Value::Object(fields) = value else {
+            return Err("expected a JSON object".to_string());
+        };
+        let field = |name: &str| fields.get(name).ok_or(format!("missing \"{}\"", name));
+        let Value::String(input) = field("input")? else {
+            return Err("\"input\" must be a string".to_string());
+        };
+        let Value::Array(collapsed) = field("collapsed")? else {
+            return Err("\"collapsed\" must be a list of paths".to_string());
+        };
+        Ok(Session {
+            input: input.clone(),
+            collapsed: collapsed
+                .iter()
+                .map(|p| json_path(p, "collapsed"))
+                .collect::<Result<_, _>>()?,
+            selected: json_path(field("selected")?, "selected")?,
+        })
+    }
+}
+
+fn json_path(value: &Value, name: &str) -> Result<Vec<usize>, String> {
+    let not_a_path = || format!("\"{}\" must hold lists of indices", name);
+    let Value::Array(steps) = value else {
+        return Err(not_a_path());
+    };
+    steps
+        .iter()
+        .map(|step| {
+            step.as_u64()
+                .and_then(|i| usize::try_from(i).ok())
+                .ok_or_else(not_a_path)
+        })
+        .collect()
+}
+
+impl App {
+    /// Opens the `Ctrl-S` (save) or `Ctrl-O` (load) session path prompt.
+    pub fn start_session_prompt(&mut self, save: bool) {
+        self.session_save = save;
+        self.session_active = true;
+    }
+
+    /// Handles a key while the session path prompt is open.
+    pub(crate) fn handle_session_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.session_active = false;
+                let path = std::mem::take(&mut self.session_buffer);
+                match path.trim() {
+                    "" => {}
+                    path if self.session_save => self.save_session(path),
+                    path => self.load_session(path),
+                }
+            }
+            KeyCode::Esc => {
+                self.session_active = false;
+                self.session_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.session_buffer.pop();
+            }
+            KeyCode::Char(c) => self.session_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// The current input and view, as saved by `Ctrl-S`. A tree opened from
+    /// a file has no input text, so its bytes are saved as hex instead.
+    pub fn session(&self) -> Session {
+        let mut collapsed: Vec<Vec<usize>> = self.collapsed_nodes.iter().cloned().collect();
+        collapsed.sort();
+        let input = if self.input_buffer.trim().is_empty() {
+            hex::encode_upper(&self.buffer)
+        } else {
+            self.input_buffer.clone()
+        };
+        Session {
+            input,
+            collapsed,
+            selected: self.selected_path.clone(),
+        }
+    }
+
+    /// Writes [`App::session`] to `path` as JSON. Failures are reported in
+    /// the error status line.
+    pub fn save_session(&mut self, path: &str) {
+        if self.parsed_objects.is_empty() {
+            self.report_error("Session save failed: nothing has been parsed".to_string());
+            return;
+        }
+        match std::fs::write(path, self.session().to_json()) {
+            Ok(()) => self.log(format!("Saved session to {}", path)),
+            Err(e) => self.report_error(format!("Session save to {} failed: {}", path, e)),
+        }
+    }
+
+    /// Reads a session file written by [`App::save_session`] and restores it.
+    /// Failures are reported in the error status line and leave the current
+    /// tree as it was.
+    pub fn load_session(&mut self, path: &str) {
+        let session = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Session::from_json(&text));
+        match session {
+            Ok(session) => {
+                if self.restore_session(session) {
+                    self.log(format!("Loaded session from {}", path));
+                }
+            }
+            Err(e) => self.report_error(format!("Session load from {} failed: {}", path, e)),
+        }
+    }
+
+    /// Puts the session's text back in the Input panel, parses it, and
+    /// restores its collapsed nodes and selection. Collapsed paths that no
+    /// longer name a constructed node are dropped, and the selection is
+    /// clamped to the tree. Returns false, keeping the current input, when
+    /// the saved input does not parse.
+    pub fn restore_session(&mut self, session: Session) -> bool {
+        let previous = std::mem::replace(&mut self.input_buffer, session.input);
+        let previous_cursor = std::mem::replace(&mut self.cursor, self.input_buffer.len());
+        if !self.parse_input() {
+            self.input_buffer = previous;
+            self.cursor = previous_cursor;
+            // The decode error described the saved text, not the restored one.
+            self.input_error = None;
+            return false;
+        }
+        self.mode = AppMode::View;
+        let saved = session.collapsed.len();
+        self.collapsed_nodes = session
+            .collapsed
+            .into_iter()
+            .filter(|path| {
+                get_by_path(&self.parsed_objects, path)
+                    .is_some_and(|obj| matches!(obj.value, OwnedValue::Constructed(_)))
+            })
+            .collect();
+        if self.collapsed_nodes.len() < saved {
+            self.log(format!(
+                "Dropped {} collapsed path(s) that no longer match the tree",
+                saved - self.collapsed_nodes.len()
+            ));
+        }
+        self.selected_path = session.selected;
+        self.clamp_selection();
+        // Stop at the outermost collapsed ancestor so the selection is visible.
+        if let Some(depth) = (1..self.selected_path.len())
+            .find(|&depth| self.collapsed_nodes.contains(&self.selected_path[..depth]))
+        {
+            self.selected_path.truncate(depth);
+        }
+        self.update_tree_scroll(self.tree_height);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::tree::tests::app_from_hex;
+    use crossterm::event::KeyModifiers;
+
+    /// SEQUENCE { SEQUENCE { INTEGER 1 }, SEQUENCE { NULL } }
+    const NESTED: &str = "3009300302010130020500";
+
+    #[test]
+    fn test_session_json_round_trips() {
+        let session = Session {
+            input: "-----BEGIN X-----\nMAMCAf8=\n-----END X-----\n".to_string(),
+            collapsed: vec![vec![0], vec![0, 1, 12]],
+            selected: vec![0, 1],
+        };
+        let json = session.to_json();
+        assert_eq!(
+            json,
+            "{\"collapsed\":[[0],[0,1,12]],\"input\":\"-----BEGIN X-----\\nMAMCAf8=\\n-----END X-----\\n\",\"selected\":[0,1]}\n"
+        );
+        assert_eq!(Session::from_json(&json), Ok(session.clone()));
+        // Any JSON formatting of the same values reads back the same.
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let pretty = serde_json::to_string_pretty(&value).unwrap();
+        assert_eq!(Session::from_json(&pretty), Ok(session));
+    }
+
+    #[test]
+    fn test_session_json_errors() {
+        assert_eq!(
+            Session::from_json("{\"input\": \"00\", \"collapsed\": []}"),
+            Err("missing \"selected\"".to_string())
+        );
+        assert_eq!(
+            Session::from_json("{\"input\": 0, \"collapsed\": [], \"selected\": []}"),
+            Err("\"input\" must be a string".to_string())
+        );
+        assert_eq!(
+            Session::from_json("{\"input\": \"\", \"collapsed\": [[-1]], \"selected\": []}"),
+            Err("\"collapsed\" must hold lists of indices".to_string())
+        );
+        assert!(Session::from_json("[1, 2]").is_err());
+        assert!(Session::from_json("{\"input\": \"00\"").is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_restore_the_view() {
+        let path =
+            std::env::temp_dir().join(format!("asn1smith-session-{}.json", std::process::id()));
+        let mut app = app_from_hex(NESTED);
+        app.input_buffer = NESTED.to_lowercase();
+        app.mode = AppMode::View;
+        app.collapsed_nodes.insert(vec![0, 0]);
+        app.selected_path = vec![0, 1, 0];
+        let mut press = |code, modifiers| app.handle_input(KeyEvent::new(code, modifiers));
+        press(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        for c in path.to_str().unwrap().chars() {
+            press(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(KeyCode::Enter, KeyModifiers::NONE);
+
+        let mut restored = app_from_hex("0500");
+        restored.start_session_prompt(false);
+        restored.session_buffer = path.to_str().unwrap().to_string();
+        restored.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(restored.last_error, None);
+        assert_eq!(restored.session(), app.session());
+        assert_eq!(restored.parsed_objects, app.parsed_objects);
+        assert_eq!(restored.mode, AppMode::View);
+        // The Input panel shows the saved text, so Ctrl-R re-parses the same tree.
+        assert_eq!(restored.input_buffer, NESTED.to_lowercase());
+        assert_eq!(restored.cursor, NESTED.len());
+        restored.mode = AppMode::Input;
+        restored.handle_input(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(restored.parsed_objects, app.parsed_objects);
+    }
+
+    #[test]
+    fn test_session_of_an_opened_file_saves_its_bytes_as_hex() {
+        let mut app = App::new();
+        assert!(app.open_file("a.der".to_string(), vec![0x05, 0x00]));
+        assert_eq!(app.session().input, "0500");
+    }
+
+    #[test]
+    fn test_restore_drops_paths_the_tree_no_longer_has() {
+        let mut app = app_from_hex("0500");
+        let restored = app.restore_session(Session {
+            input: NESTED.to_string(),
+            // A primitive, a missing node, and a real constructed node.
+            collapsed: vec![vec![0, 0, 0], vec![0, 5], vec![0, 1]],
+            selected: vec![0, 1, 0],
+        });
+        assert!(restored);
+        assert_eq!(app.session().collapsed, vec![vec![0, 1]]);
+        assert_eq!(app.selected_path, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_restore_keeps_current_input_when_saved_input_fails() {
+        let mut app = app_from_hex("0500");
+        app.input_buffer = "0500".to_string();
+        let restored = app.restore_session(Session {
+            input: "300502".to_string(),
+            collapsed: Vec::new(),
+            selected: vec![0],
+        });
+        assert!(!restored);
+        assert_eq!(app.input_buffer, "0500");
+        assert_eq!(app.buffer, vec![0x05, 0x00]);
+        assert_eq!(app.parsed_objects.len(), 1);
+        assert!(
+            app.last_error
+                .as_deref()
+                .unwrap()
+                .starts_with("Parse failed: ")
+        );
+    }
+}
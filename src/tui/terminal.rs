@@ -0,0 +1,67 @@
+// src/tui/terminal.rs
+use crossterm::cursor::Show;
+use crossterm::execute;
+use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
+
+/// Leaves raw mode and the alternate screen and shows the cursor again.
+/// Errors are ignored, since this runs while exiting or unwinding.
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(std::io::stdout(), LeaveAlternateScreen, Show);
+}
+
+/// Runs `restore` when dropped, so the terminal is put back however the TUI
+/// loop ends: normally, through an early `?` return, or by a panic.
+pub struct TerminalGuard<F: FnMut()> {
+    restore: F,
+}
+
+impl<F: FnMut()> TerminalGuard<F> {
+    pub fn new(restore: F) -> Self {
+        Self { restore }
+    }
+}
+
+impl<F: FnMut()> Drop for TerminalGuard<F> {
+    fn drop(&mut self) {
+        (self.restore)();
+    }
+}
+
+/// Chains a panic hook that restores the terminal before the previous hook
+/// prints the message, which would otherwise land on the alternate screen
+/// and vanish with it.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_guard_restores_on_drop() {
+        let calls = Cell::new(0);
+        {
+            let _guard = TerminalGuard::new(|| calls.set(calls.get() + 1));
+            assert_eq!(calls.get(), 0);
+        }
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_guard_restores_when_unwinding() {
+        let calls = Cell::new(0);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = TerminalGuard::new(|| calls.set(calls.get() + 1));
+            panic!("draw failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+}
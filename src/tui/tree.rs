@@ -1,8 +1,11 @@
 // src/tui/tree.rs
-use crate::der_parser::{OwnedObject, TagClass};
+use crate::der_encoder::encode_content;
+use crate::der_parser::{OwnedObject, TagClass, get_by_path};
+use crate::format::{DEFAULT_INDENT_WIDTH, Indent};
 use crate::tui::app::App;
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::ListItem;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub fn tag_name(class: &TagClass, number: u32) -> Option<&'static str> {
     match (class, number) {
@@ -12,140 +15,597 @@ pub fn tag_name(class: &TagClass, number: u32) -> Option<&'static str> {
         (TagClass::Universal, 4) => Some("OCTET STRING"),
         (TagClass::Universal, 5) => Some("NULL"),
         (TagClass::Universal, 6) => Some("OBJECT IDENTIFIER"),
+        (TagClass::Universal, 7) => Some("ObjectDescriptor"),
+        (TagClass::Universal, 9) => Some("REAL"),
         (TagClass::Universal, 10) => Some("ENUMERATED"),
+        (TagClass::Universal, 12) => Some("UTF8String"),
+        (TagClass::Universal, 13) => Some("RELATIVE-OID"),
         (TagClass::Universal, 16) => Some("SEQUENCE"),
         (TagClass::Universal, 17) => Some("SET"),
+        (TagClass::Universal, 18) => Some("NumericString"),
         (TagClass::Universal, 19) => Some("PrintableString"),
         (TagClass::Universal, 20) => Some("T61String"),
+        (TagClass::Universal, 21) => Some("VideotexString"),
         (TagClass::Universal, 22) => Some("IA5String"),
         (TagClass::Universal, 23) => Some("UTCTime"),
         (TagClass::Universal, 24) => Some("GeneralizedTime"),
+        (TagClass::Universal, 25) => Some("GraphicString"),
+        (TagClass::Universal, 26) => Some("VisibleString"),
+        (TagClass::Universal, 27) => Some("GeneralString"),
+        (TagClass::Universal, 28) => Some("UniversalString"),
+        (TagClass::Universal, 30) => Some("BMPString"),
         _ => None,
     }
 }
 
+/// Formats a tag as "NAME (number)" when the name is known, otherwise just the
+/// number. Non-universal tags use ASN.1 notation: `[0]`, `[APPLICATION 2]`,
+/// `[PRIVATE 5]`.
+pub fn tag_display(object: &OwnedObject) -> String {
+    let number = object.tag.number;
+    match object.tag.class {
+        TagClass::ContextSpecific => format!("[{}]", number),
+        TagClass::Application => format!("[APPLICATION {}]", number),
+        TagClass::Private => format!("[PRIVATE {}]", number),
+        TagClass::Universal => match tag_name(&object.tag.class, number) {
+            Some(name) => format!("{} ({})", name, number),
+            None => number.to_string(),
+        },
+    }
+}
+
+/// Builds the status bar line for the node at `path`: its path, tag class,
+/// number and name, content length, and the byte range it occupies in the
+/// parsed buffer (edited nodes no longer have one).
+pub fn selection_status(path: &[usize], object: &OwnedObject) -> String {
+    let path = path
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(" > ");
+    let mut tag = format!("{:?} {}", object.tag.class, object.tag.number);
+    if let Some(name) = tag_name(&object.tag.class, object.tag.number) {
+        tag.push(' ');
+        tag.push_str(name);
+    }
+    let range = if object.tlv_end > 0 {
+        format!("bytes {}..{}", object.tlv_start, object.tlv_end)
+    } else {
+        "bytes: edited".to_string()
+    };
+    format!("{} │ {} │ len {} │ {}", path, tag, object.length, range)
+}
+
+/// Formats the decoded value shown after the tag in a tree label.
+/// UTCTime years are read with `utc_year_pivot`.
+pub fn value_display(object: &OwnedObject, utc_year_pivot: u32) -> String {
+    match &object.value {
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 1 =>
+        {
+            // Any nonzero byte reads as true; non-canonical forms are flagged
+            // separately by the validator.
+            (bytes.iter().any(|&b| b != 0)).to_string()
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 2 =>
+        {
+            // DER INTEGERs are two's-complement, so 02 01 FF is -1, not 255.
+            // The bit length (of the magnitude) is what e.g. RSA key sizes are
+            // quoted in; non-minimal encodings are flagged by the validator.
+            let value = num_bigint::BigInt::from_signed_bytes_be(bytes);
+            format!("{} ({}-bit)", value, value.bits())
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 10 =>
+        {
+            crate::decode::format_enumerated(bytes, &[])
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 6 =>
+        {
+            match crate::oid::decode_oid(bytes) {
+                Ok(oid) => crate::oid::describe_oid(&oid),
+                Err(e) => format!("<invalid OID: {:?}> {:?}", e, bytes),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal
+                && object.tag.number == 3
+                && bytes.len() <= crate::decode::NAMED_BITS_MAX_BYTES + 1 =>
+        {
+            match crate::decode::bit_string_bits(bytes) {
+                Some((unused, bits)) => format!(
+                    "bits set: {}, {} unused: {}",
+                    crate::decode::format_bit_set(&bits),
+                    unused,
+                    hex::encode_upper(bytes)
+                ),
+                None => format!("<invalid BIT STRING> {:?}", bytes),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 13 =>
+        {
+            match crate::oid::decode_relative_oid(bytes) {
+                Ok(arcs) => arcs,
+                Err(e) => format!("<invalid RELATIVE-OID: {:?}> {:?}", e, bytes),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 9 =>
+        {
+            match crate::decode::decode_real(bytes) {
+                Some(value) => crate::decode::format_real(value),
+                None => format!("<invalid REAL> {:?}", bytes),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal
+                && matches!(object.tag.number, 7 | 18 | 21 | 25..=28) =>
+        {
+            match crate::decode::decode_restricted_string(object.tag.number, bytes) {
+                Some(text) => format!("'{}'", text),
+                None => format!("<invalid {}>", tag_display(object)),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal && object.tag.number == 30 =>
+        {
+            match crate::decode::decode_bmp_string(bytes) {
+                Some(text) => format!("'{}'", text),
+                None => "<invalid BMPString>".to_string(),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes)
+            if object.tag.class == TagClass::Universal
+                && (object.tag.number == 23 || object.tag.number == 24) =>
+        {
+            let raw = String::from_utf8_lossy(bytes);
+            match crate::asn1_time::parse_asn1_time_with_pivot(
+                bytes,
+                object.tag.number == 24,
+                utc_year_pivot,
+            ) {
+                Some(time) => format!("'{}' → {}", raw, time.to_iso8601()),
+                None => format!("'{}' → <invalid time>", raw),
+            }
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes) => {
+            let string_value = match (&object.tag.class, object.tag.number) {
+                (TagClass::Universal, 12) |
+                (TagClass::Universal, 19) |
+                (TagClass::Universal, 20) |
+                (TagClass::Universal, 22)   // IA5String
+                    => std::str::from_utf8(bytes).ok(),
+                _ => None,
+            };
+            if let Some(s) = string_value {
+                format!("'{}'", s)
+            } else {
+                format!("{:?}", bytes)
+            }
+        }
+        crate::der_parser::OwnedValue::Constructed(children) => {
+            format!("Constructed ({} children)", children.len())
+        }
+    }
+}
+
+/// The per-node state of a tree view: which node is selected, which are
+/// collapsed or decoded as another type, and what is being searched for.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeView<'v> {
+    pub selected_path: &'v [usize],
+    pub collapsed_nodes: &'v HashSet<Vec<usize>>,
+    /// Universal types chosen with `D` for primitives' values.
+    pub decode_overrides: &'v HashMap<Vec<usize>, u32>,
+    pub search_query: &'v str,
+}
+
 pub fn tui_list_items<'a>(
     objects: &'a [OwnedObject],
-    selected_path: &[usize],
-    collapsed_nodes: &HashSet<Vec<usize>>,
+    view: TreeView,
+    options: DisplayOptions,
 ) -> (Vec<ListItem<'a>>, usize) {
     let mut items = Vec::new();
     let mut path = vec![0];
     let mut selected_idx = 0;
+    let ctx = RenderContext {
+        view,
+        field_labels: crate::x509::field_labels(objects),
+        schema_labels: crate::schema::schema_labels(objects, crate::schema::BUILTIN_SCHEMAS),
+        rdn_labels: if options.dn_labels {
+            crate::x509::rdn_labels(objects)
+        } else {
+            HashMap::new()
+        },
+        algorithm_labels: if options.dn_labels {
+            crate::x509::algorithm_labels(objects)
+        } else {
+            HashMap::new()
+        },
+        options,
+    };
     for (i, obj) in objects.iter().enumerate() {
         path[0] = i;
         render_object_with_index(
             obj,
-            0,
+            &mut vec![],
             &mut path,
-            selected_path,
+            &ctx,
             &mut items,
-            collapsed_nodes,
             &mut selected_idx,
         );
     }
     (items, selected_idx)
 }
 
-fn render_object_with_index<'a>(
-    object: &OwnedObject,
-    depth: usize,
-    path: &mut Vec<usize>,
-    selected_path: &[usize],
-    items: &mut Vec<ListItem<'a>>,
-    collapsed_nodes: &HashSet<Vec<usize>>,
-    selected_idx: &mut usize,
-) {
-    use ratatui::style::{Color, Modifier, Style};
-    let indent = "  ".repeat(depth);
-    let tag_display = if let Some(name) = tag_name(&object.tag.class, object.tag.number) {
-        format!("{} ({})", name, object.tag.number)
-    } else {
-        object.tag.number.to_string()
+/// Characters of a primitive value shown in a tree label by default; the
+/// hex modal shows the full value.
+pub const DEFAULT_VALUE_WIDTH: usize = 40;
+
+/// View settings that change how node labels are rendered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayOptions {
+    /// Maximum characters of a primitive value shown in a label.
+    pub value_width: usize,
+    /// Whether RDN SETs in names are labelled `CN=…` and AlgorithmIdentifier
+    /// SEQUENCEs with their algorithm's name.
+    pub dn_labels: bool,
+    /// Whether primitive values are decoded; when off they are shown as raw
+    /// hex bytes.
+    pub pretty: bool,
+    /// Whether an expanded constructed node with a single primitive child
+    /// (e.g. an EXPLICIT `[0] { INTEGER }`) shares one row with that child.
+    pub inline_single_child: bool,
+    /// Indentation per nesting level of the tree guides (`--indent`).
+    pub indent: Indent,
+    /// The time (Unix seconds) certificate Validity fields are checked against.
+    pub now: i64,
+    /// Where two-digit UTCTime years switch from 20xx to 19xx (`--utc-year-pivot`).
+    pub utc_year_pivot: u32,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            value_width: DEFAULT_VALUE_WIDTH,
+            dn_labels: true,
+            pretty: true,
+            inline_single_child: false,
+            indent: Indent::default(),
+            now: crate::asn1_time::now_unix_seconds(),
+            utc_year_pivot: crate::asn1_time::RFC5280_YEAR_PIVOT,
+        }
+    }
+}
+
+/// The only child of `object` when it is a constructed node with exactly one
+/// child and that child is primitive, i.e. a wrapper that can share its row.
+pub fn single_primitive_child(object: &OwnedObject) -> Option<&OwnedObject> {
+    match &object.value {
+        crate::der_parser::OwnedValue::Constructed(children) => match children.as_slice() {
+            [child] if matches!(child.value, crate::der_parser::OwnedValue::Primitive(_)) => {
+                Some(child)
+            }
+            _ => None,
+        },
+        crate::der_parser::OwnedValue::Primitive(_) => None,
+    }
+}
+
+/// Shortens `text` to at most `width` characters, ending in `…` when
+/// anything was cut.
+pub fn truncate_preview(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut preview: String = text.chars().take(width.saturating_sub(1)).collect();
+    preview.push('…');
+    preview
+}
+
+/// Builds the `tree(1)`-style guide in front of a node's label. `last_flags`
+/// holds, for the node and each of its ancestors below the top level, whether
+/// it is the last child of its parent (outermost first). Top-level nodes get
+/// no prefix.
+///
+/// Each level takes the `indent` width plus one column for the guide. Widths
+/// under 2 get the narrowest guide that fits `├─`. A list row can't hold tabs,
+/// so [`Indent::Tab`] gets the default width.
+pub fn indent_prefix(last_flags: &[bool], indent: Indent) -> String {
+    let Some((&is_last, ancestors)) = last_flags.split_last() else {
+        return String::new();
+    };
+    let width = match indent {
+        Indent::Spaces(width) => width.max(DEFAULT_INDENT_WIDTH),
+        Indent::Tab => DEFAULT_INDENT_WIDTH,
+    };
+    let blank = " ".repeat(width);
+    let mut prefix: String = ancestors
+        .iter()
+        .map(|&last| format!("{}{}", if last { " " } else { "│" }, blank))
+        .collect();
+    let corner = if is_last { "└" } else { "├" };
+    prefix.push_str(&format!("{}{} ", corner, "─".repeat(width - 1)));
+    prefix
+}
+
+/// Chooses a row's style: the selection (yellow, bold) wins over a search
+/// match (magenta), which wins over the color for the node's kind:
+/// constructed universal containers are light blue, context-specific tags
+/// cyan, and character string and OID primitives green.
+pub fn node_style(object: &OwnedObject, selected: bool, search_match: bool) -> Style {
+    if selected {
+        return Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+    }
+    if search_match {
+        return Style::default().fg(Color::Magenta);
+    }
+    let tag = &object.tag;
+    let color = match tag.class {
+        TagClass::ContextSpecific => Some(Color::Cyan),
+        TagClass::Universal if tag.constructed => Some(Color::LightBlue),
+        TagClass::Universal if matches!(tag.number, 6 | 12 | 13 | 18..=22 | 25..=30) => {
+            Some(Color::Green)
+        }
+        _ => None,
+    };
+    color.map_or_else(Style::default, |c| Style::default().fg(c))
+}
+
+/// Per-render state shared by every node while flattening the tree.
+struct RenderContext<'c> {
+    view: TreeView<'c>,
+    field_labels: HashMap<Vec<usize>, &'static str>,
+    /// Field names from [`crate::schema::BUILTIN_SCHEMAS`], shown after the
+    /// header unless `field_labels` already names the node.
+    schema_labels: HashMap<Vec<usize>, &'static str>,
+    /// `CN=…`-style labels for RDN SETs, empty when the `t` toggle is off.
+    rdn_labels: HashMap<Vec<usize>, String>,
+    /// Algorithm names for AlgorithmIdentifier SEQUENCEs, under the same toggle.
+    algorithm_labels: HashMap<Vec<usize>, &'static str>,
+    options: DisplayOptions,
+}
+
+/// The child of the node at `path` that is drawn on the node's own row, if
+/// any: an expanded node's single primitive child when inlining is on.
+fn inlined_child<'o>(
+    object: &'o OwnedObject,
+    path: &[usize],
+    ctx: &RenderContext,
+) -> Option<&'o OwnedObject> {
+    if !ctx.options.inline_single_child || ctx.view.collapsed_nodes.contains(path) {
+        return None;
+    }
+    single_primitive_child(object)
+}
+
+/// A node's label without its indent guide, and whether its children are
+/// hidden, either by collapsing or by being drawn on this row.
+fn node_label(object: &OwnedObject, path: &mut Vec<usize>, ctx: &RenderContext) -> (String, bool) {
+    let mut header = format!("{} [len {}]", tag_display(object), object.length);
+    if let Some(name) = ctx.schema_labels.get(path.as_slice())
+        && !ctx.field_labels.contains_key(path.as_slice())
+    {
+        header.push_str(&format!(" ({})", name));
+    }
+    if let Some(algorithm) = ctx.algorithm_labels.get(path.as_slice()) {
+        header.push_str(&format!(" (AlgorithmIdentifier: {})", algorithm));
+    }
+    let decoded;
+    let shown = match (
+        &object.value,
+        ctx.view.decode_overrides.get(path.as_slice()),
+    ) {
+        (crate::der_parser::OwnedValue::Primitive(_), Some(&number)) => {
+            decoded = crate::tui::decode_as::decoded_as(object, number);
+            let name = tag_name(&TagClass::Universal, number).unwrap_or_default();
+            header.push_str(&format!(" as {}", name));
+            &decoded
+        }
+        _ => object,
     };
     let (label, is_collapsed) = match &object.value {
-        crate::der_parser::OwnedValue::Primitive(bytes) => {
-            let string_value = match (&object.tag.class, object.tag.number) {
-                (TagClass::Universal, 19) |
-                (TagClass::Universal, 20) |
-                (TagClass::Universal, 22) |
-                (TagClass::Universal, 23) |
-                (TagClass::Universal, 24)   // GeneralizedTime
-                    => std::str::from_utf8(bytes).ok(),
-                _ => None,
+        crate::der_parser::OwnedValue::Primitive(_) if let Some(wrapped) = &object.wrapped => {
+            let collapsed = ctx.view.collapsed_nodes.contains(path);
+            let marker = if collapsed { "▶" } else { "▼" };
+            let label = format!("{} {}: wraps {} object(s)", marker, header, wrapped.len());
+            (label, collapsed)
+        }
+        crate::der_parser::OwnedValue::Primitive(bytes) if !ctx.options.pretty => {
+            let value = truncate_preview(&format!("{:02X?}", bytes), ctx.options.value_width);
+            (format!("{}: {}", header, value), false)
+        }
+        crate::der_parser::OwnedValue::Primitive(_) => {
+            let value = truncate_preview(
+                &value_display(shown, ctx.options.utc_year_pivot),
+                ctx.options.value_width,
+            );
+            let label = match ctx.field_labels.get(path.as_slice()) {
+                Some(field) => {
+                    let status = crate::x509::describe_validity(
+                        field,
+                        object,
+                        ctx.options.now,
+                        ctx.options.utc_year_pivot,
+                    );
+                    format!(
+                        "{} ({}): {}{}",
+                        header,
+                        field,
+                        value,
+                        status.unwrap_or_default()
+                    )
+                }
+                None => format!("{}: {}", header, value),
             };
-            let value_display = if let Some(s) = string_value {
-                format!("'{}'", s)
+            (label, false)
+        }
+        crate::der_parser::OwnedValue::Constructed(_) => {
+            if let Some(child) = inlined_child(object, path, ctx) {
+                path.push(0);
+                let (child_label, _) = node_label(child, path, ctx);
+                path.pop();
+                (format!("▼ {} {}", header, child_label), true)
             } else {
-                format!("{:?}", bytes)
-            };
-            (
-                format!("{}{}: {}", indent, tag_display, value_display),
-                false,
-            )
+                let collapsed = ctx.view.collapsed_nodes.contains(path);
+                let marker = if collapsed { "▶" } else { "▼" };
+                let value = match ctx.rdn_labels.get(path.as_slice()) {
+                    Some(rdn) => rdn.clone(),
+                    None => value_display(object, ctx.options.utc_year_pivot),
+                };
+                (format!("{} {}: {}", marker, header, value), collapsed)
+            }
         }
-        crate::der_parser::OwnedValue::Constructed(children) => {
-            let collapsed = collapsed_nodes.contains(path);
-            let marker = if collapsed { "▶" } else { "▼" };
-            (
-                format!(
-                    "{}{} {}: Constructed ({} children)",
-                    indent,
-                    marker,
-                    tag_display,
-                    children.len()
-                ),
-                collapsed,
-            )
+    };
+    let label = match object.tag.form_warning() {
+        Some(warning) => format!("{} ⚠ {}", label, warning),
+        None => label,
+    };
+    let label = match &object.value {
+        crate::der_parser::OwnedValue::Primitive(bytes) => {
+            match crate::validate::primitive_warning(&object.tag, bytes) {
+                Some(warning) => format!("{} ⚠ {}", label, warning),
+                None => label,
+            }
         }
+        crate::der_parser::OwnedValue::Constructed(_) => label,
     };
-    let is_selected = path == selected_path;
+    (label, is_collapsed)
+}
+
+fn render_object_with_index<'a>(
+    object: &OwnedObject,
+    last_flags: &mut Vec<bool>,
+    path: &mut Vec<usize>,
+    ctx: &RenderContext,
+    items: &mut Vec<ListItem<'a>>,
+    selected_idx: &mut usize,
+) {
+    let (label, is_collapsed) = node_label(object, path, ctx);
+    let label = format!("{}{}", indent_prefix(last_flags, ctx.options.indent), label);
+    // An inlined child has no row of its own, so selecting it selects this one.
+    let is_selected = path == ctx.view.selected_path
+        || (inlined_child(object, path, ctx).is_some()
+            && ctx.view.selected_path.len() == path.len() + 1
+            && ctx.view.selected_path.starts_with(path));
     if is_selected {
         *selected_idx = items.len();
     }
-    let item = if is_selected {
-        ListItem::new(label).style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )
-    } else {
-        ListItem::new(label)
-    };
-    items.push(item);
-    if let crate::der_parser::OwnedValue::Constructed(children) = &object.value {
-        if !is_collapsed {
-            for (i, child) in children.iter().enumerate() {
-                path.push(i);
-                render_object_with_index(
-                    child,
-                    depth + 1,
-                    path,
-                    selected_path,
-                    items,
-                    collapsed_nodes,
-                    selected_idx,
-                );
-                path.pop();
-            }
+    let search_match = !ctx.view.search_query.is_empty()
+        && crate::tui::search::node_matches(
+            object,
+            ctx.view.search_query,
+            ctx.options.utc_year_pivot,
+        );
+    items.push(ListItem::new(label).style(node_style(object, is_selected, search_match)));
+    if let Some(children) = object.children()
+        && !is_collapsed
+    {
+        for (i, child) in children.iter().enumerate() {
+            path.push(i);
+            last_flags.push(i + 1 == children.len());
+            render_object_with_index(child, last_flags, path, ctx, items, selected_idx);
+            last_flags.pop();
+            path.pop();
         }
     }
 }
 
+/// The pinned and selected nodes, formatted for side-by-side display.
+#[derive(Debug, PartialEq)]
+pub struct NodeComparison {
+    pub pinned: String,
+    pub selected: String,
+    pub bytes_equal: bool,
+}
+
 impl App {
+    /// Pins the selected node for comparison, or unpins it if it is already pinned.
+    pub fn toggle_pin(&mut self) {
+        if self.pinned_path.as_ref() == Some(&self.selected_path) {
+            self.pinned_path = None;
+            self.show_compare_modal = false;
+        } else if self.get_selected_object().is_some() {
+            self.pinned_path = Some(self.selected_path.clone());
+        }
+    }
+
+    /// Compares the content bytes of the pinned node against the selected one.
+    /// Tags are ignored so that e.g. an implicitly tagged key identifier can be
+    /// matched against a plain OCTET STRING.
+    pub fn compare_pinned(&self) -> Option<NodeComparison> {
+        let pinned = get_by_path(&self.parsed_objects, self.pinned_path.as_ref()?)?;
+        let selected = self.get_selected_object()?;
+        let pivot = self.render_options.utc_year_pivot;
+        let describe =
+            |obj: &OwnedObject| format!("{}: {}", tag_display(obj), value_display(obj, pivot));
+        Some(NodeComparison {
+            pinned: describe(pinned),
+            selected: describe(selected),
+            bytes_equal: encode_content(pinned) == encode_content(selected),
+        })
+    }
+
+    /// Clamps the selection onto an existing node before a move, returning
+    /// false when there is nothing to select and the move should do nothing.
+    fn ensure_selection(&mut self) -> bool {
+        self.clamp_selection();
+        // A child drawn on its parent's row is navigated as that row.
+        if let Some((_, parent)) = self.selected_path.split_last()
+            && !parent.is_empty()
+            && self.inlines_child(parent)
+        {
+            self.selected_path.pop();
+        }
+        !self.selected_path.is_empty()
+    }
+
+    /// Whether the node at `path` is drawn together with its single
+    /// primitive child, which then has no row of its own.
+    fn inlines_child(&self, path: &[usize]) -> bool {
+        self.inline_single_child
+            && !self.collapsed_nodes.contains(path)
+            && get_by_path(&self.parsed_objects, path)
+                .and_then(single_primitive_child)
+                .is_some()
+    }
+
+    /// Whether the children of the node at `path` have no rows of their own,
+    /// because it is collapsed or its only child is inlined.
+    fn hides_children(&self, path: &[usize]) -> bool {
+        self.collapsed_nodes.contains(path) || self.inlines_child(path)
+    }
+
     pub fn move_selection_up(&mut self, area_height: usize) {
-        if self.selected_path.is_empty() {
+        if !self.ensure_selection() {
+            return;
+        }
+        self.step_selection_up();
+        self.update_tree_scroll(area_height);
+    }
+
+    pub fn move_selection_down(&mut self, area_height: usize) {
+        if !self.ensure_selection() {
             return;
         }
+        self.step_selection_down();
+        self.update_tree_scroll(area_height);
+    }
+
+    /// Selects the row above the selected one, leaving `tree_scroll` alone.
+    fn step_selection_up(&mut self) {
         if let Some(current_idx) = self.selected_path.last_mut() {
             if *current_idx > 0 {
                 *current_idx -= 1;
                 // Move to the last visible descendant of the previous sibling
                 while let Some(obj) = self.get_selected_object() {
-                    if let crate::der_parser::OwnedValue::Constructed(children) = &obj.value {
-                        if !children.is_empty()
-                            && !self.collapsed_nodes.contains(&self.selected_path)
-                        {
+                    if let Some(children) = obj.children() {
+                        if !children.is_empty() && !self.hides_children(&self.selected_path) {
                             self.selected_path.push(children.len() - 1);
                         } else {
                             break;
@@ -158,24 +618,21 @@ impl App {
                 self.selected_path.pop();
             }
         }
-        self.update_tree_scroll(area_height);
     }
 
-    pub fn move_selection_down(&mut self, area_height: usize) {
+    /// Selects the row below the selected one, leaving `tree_scroll` alone.
+    fn step_selection_down(&mut self) {
         // Try to descend into children if possible
         let can_descend = {
             let obj = self.get_selected_object();
-            obj.map_or(false, |o| {
-                if let crate::der_parser::OwnedValue::Constructed(ref children) = o.value {
-                    !children.is_empty() && !self.collapsed_nodes.contains(&self.selected_path)
-                } else {
-                    false
-                }
+            obj.is_some_and(|o| {
+                o.children().is_some_and(|children| {
+                    !children.is_empty() && !self.hides_children(&self.selected_path)
+                })
             })
         };
         if can_descend {
             self.selected_path.push(0);
-            self.update_tree_scroll(area_height);
             return;
         }
         // Otherwise, try to move to the next sibling or ancestor's next sibling
@@ -188,44 +645,213 @@ impl App {
                 }
                 p
             };
-            if get_object_by_path(&self.parsed_objects, &check_path).is_some() {
+            if get_by_path(&self.parsed_objects, &check_path).is_some() {
                 self.selected_path = check_path;
-                self.update_tree_scroll(area_height);
                 return;
             }
             path.pop();
         }
-        self.update_tree_scroll(area_height);
     }
 
-    pub fn toggle_collapse(&mut self) {
-        if self.get_selected_object().map_or(false, |obj| {
-            matches!(obj.value, crate::der_parser::OwnedValue::Constructed(_))
-        }) {
-            if !self.collapsed_nodes.remove(&self.selected_path) {
-                self.collapsed_nodes.insert(self.selected_path.clone());
-            }
+    /// Number of children of the selected node, or `None` for a primitive
+    /// that wraps nothing.
+    fn selected_child_count(&self) -> Option<usize> {
+        Some(self.get_selected_object()?.children()?.len())
+    }
+
+    /// `l`/Right: expands a collapsed node, or moves into the first child of
+    /// an expanded one.
+    pub fn expand_or_descend(&mut self) {
+        if !self.ensure_selection() {
+            return;
         }
+        if self.collapsed_nodes.remove(&self.selected_path) {
+            return;
+        }
+        if self.selected_child_count().is_some_and(|n| n > 0)
+            && !self.inlines_child(&self.selected_path)
+        {
+            self.selected_path.push(0);
+            self.update_tree_scroll(self.tree_height);
+        }
+    }
+
+    /// `h`/Left: collapses an expanded node with children, or moves to the
+    /// parent of a collapsed node, an empty one, or a primitive.
+    pub fn collapse_or_ascend(&mut self) {
+        if !self.ensure_selection() {
+            return;
+        }
+        if self.selected_child_count().is_some_and(|n| n > 0)
+            && self.collapsed_nodes.insert(self.selected_path.clone())
+        {
+            return;
+        }
+        if self.selected_path.len() > 1 {
+            self.selected_path.pop();
+            self.update_tree_scroll(self.tree_height);
+        }
+    }
+
+    /// Sets `fold_depth` and collapses every constructed node at that depth
+    /// or deeper, replacing any manual collapsing; `None` expands everything.
+    /// A selection hidden by the fold moves up to its collapsed ancestor.
+    pub fn set_fold_depth(&mut self, depth: Option<usize>) {
+        self.fold_depth = depth;
+        self.collapsed_nodes = match depth {
+            Some(depth) => {
+                self.selected_path.truncate(depth + 1);
+                collapsed_below_depth(&self.parsed_objects, depth)
+            }
+            None => HashSet::new(),
+        };
+        self.update_tree_scroll(self.tree_height);
+    }
+
+    /// Folds one level deeper (`+`) or shallower (`-`). Going deeper than the
+    /// deepest constructed node turns folding off, and going shallower with
+    /// folding off starts from that deepest level.
+    pub fn change_fold_depth(&mut self, deeper: bool) {
+        let Some(deepest) = deepest_constructed(&self.parsed_objects) else {
+            return;
+        };
+        let depth = match (self.fold_depth, deeper) {
+            (Some(depth), true) if depth >= deepest => None,
+            (Some(depth), true) => Some(depth + 1),
+            (Some(depth), false) => Some(depth.saturating_sub(1)),
+            (None, true) => None,
+            (None, false) => Some(deepest),
+        };
+        self.set_fold_depth(depth);
     }
 
     pub fn get_selected_object(&self) -> Option<&OwnedObject> {
-        let mut current = self.parsed_objects.get(0)?;
+        get_by_path(&self.parsed_objects, &self.selected_path)
+    }
+
+    /// Moves `selected_path` to the nearest node that exists: each index is
+    /// capped at the last child at its level, and the path stops at a node
+    /// without children. The first object is selected when the path is
+    /// empty, and nothing when there are no objects.
+    pub fn clamp_selection(&mut self) {
+        let Some(last) = self.parsed_objects.len().checked_sub(1) else {
+            self.selected_path.clear();
+            return;
+        };
+        let mut clamped = vec![self.selected_path.first().map_or(0, |&i| i.min(last))];
+        let mut current = &self.parsed_objects[clamped[0]];
         for &idx in self.selected_path.iter().skip(1) {
-            if let crate::der_parser::OwnedValue::Constructed(children) = &current.value {
-                current = children.get(idx)?;
+            let Some(children) = current.children() else {
+                break;
+            };
+            let Some(last) = children.len().checked_sub(1) else {
+                break;
+            };
+            clamped.push(idx.min(last));
+            current = &children[idx.min(last)];
+        }
+        self.selected_path = clamped;
+    }
+
+    /// The selection, collapsed nodes, decode overrides and search of the
+    /// shown tree.
+    pub fn tree_view(&self) -> TreeView<'_> {
+        TreeView {
+            selected_path: &self.selected_path,
+            collapsed_nodes: &self.collapsed_nodes,
+            decode_overrides: &self.decode_overrides,
+            search_query: &self.search_buffer,
+        }
+    }
+
+    /// The label settings currently chosen in the app.
+    pub fn display_options(&self) -> DisplayOptions {
+        DisplayOptions {
+            value_width: self.value_preview_width,
+            dn_labels: self.show_dn_labels,
+            pretty: self.pretty,
+            inline_single_child: self.inline_single_child,
+            indent: self.render_options.indent,
+            now: crate::asn1_time::now_unix_seconds(),
+            utc_year_pivot: self.render_options.utc_year_pivot,
+        }
+    }
+
+    /// Where the selected node's TLV sits in `buffer`, or `None` when it has
+    /// no source offsets (e.g. it was edited).
+    pub fn selected_buffer_range(&self) -> Option<std::ops::Range<usize>> {
+        let obj = self.get_selected_object()?;
+        (obj.tlv_end > 0 && obj.tlv_end <= self.buffer.len()).then_some(obj.tlv_start..obj.tlv_end)
+    }
+
+    /// The status bar text for the current selection, if any.
+    pub fn selected_status(&self) -> Option<String> {
+        self.get_selected_object()
+            .map(|obj| selection_status(&self.selected_path, obj))
+    }
+
+    /// Selects the first node (`gg`).
+    pub fn select_first(&mut self) {
+        if self.parsed_objects.is_empty() {
+            return;
+        }
+        self.selected_path = vec![0];
+        self.update_tree_scroll(self.tree_height);
+    }
+
+    /// Selects the last visible node (`G`): the deepest last descendant of the
+    /// last top-level object that isn't hidden by a collapsed ancestor.
+    pub fn select_last(&mut self) {
+        let Some(last) = self.parsed_objects.len().checked_sub(1) else {
+            return;
+        };
+        let mut path = vec![last];
+        while let Some(obj) = get_by_path(&self.parsed_objects, &path)
+            && let Some(children) = obj.children()
+            && !children.is_empty()
+            && !self.hides_children(&path)
+        {
+            path.push(children.len() - 1);
+        }
+        self.selected_path = path;
+        self.update_tree_scroll(self.tree_height);
+    }
+
+    /// Moves the selection and the view by one page of `tree_height` rows, so
+    /// the selected row keeps its position on screen where possible.
+    pub fn page_tree(&mut self, down: bool) {
+        if !self.ensure_selection() {
+            return;
+        }
+        let page = self.tree_height.max(1);
+        let scroll = self.tree_scroll;
+        for _ in 0..page {
+            if down {
+                self.step_selection_down();
             } else {
-                return None;
+                self.step_selection_up();
             }
         }
-        Some(current)
+        let (items, selected_idx) = tui_list_items(
+            &self.parsed_objects,
+            self.tree_view(),
+            self.display_options(),
+        );
+        let max_scroll = items.len().saturating_sub(page);
+        let scroll = if down {
+            (scroll + page).min(max_scroll)
+        } else {
+            scroll.saturating_sub(page)
+        };
+        self.tree_scroll = scroll.clamp(selected_idx.saturating_sub(page - 1), selected_idx);
     }
 
     /// Call this after changing selection to ensure selected item is visible.
     pub fn update_tree_scroll(&mut self, area_height: usize) {
-        let (items, selected_idx) = crate::tui::tree::tui_list_items(
+        let (_, selected_idx) = crate::tui::tree::tui_list_items(
             &self.parsed_objects,
-            &self.selected_path,
-            &self.collapsed_nodes,
+            self.tree_view(),
+            self.display_options(),
         );
         if selected_idx < self.tree_scroll {
             self.tree_scroll = selected_idx;
@@ -235,16 +861,855 @@ impl App {
     }
 }
 
-fn get_object_by_path<'a>(objects: &'a [OwnedObject], path: &[usize]) -> Option<&'a OwnedObject> {
-    let mut current = objects.get(*path.get(0)?);
-    for &idx in path.iter().skip(1) {
-        current = match current {
-            Some(obj) => match &obj.value {
-                crate::der_parser::OwnedValue::Constructed(children) => children.get(idx),
-                _ => return None,
+/// Whether two trees have the same tags and nesting, ignoring primitive
+/// values. Paths into one then address the same kind of node in the other.
+pub fn same_shape(a: &[OwnedObject], b: &[OwnedObject]) -> bool {
+    use crate::der_parser::OwnedValue;
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(x, y)| {
+            x.tag == y.tag
+                && match (&x.value, &y.value) {
+                    (OwnedValue::Primitive(_), OwnedValue::Primitive(_)) => true,
+                    (OwnedValue::Constructed(xs), OwnedValue::Constructed(ys)) => {
+                        same_shape(xs, ys)
+                    }
+                    _ => false,
+                }
+        })
+}
+
+/// Paths of every node with children at `depth` or deeper (top-level nodes
+/// are at depth 0), i.e. the nodes to collapse so that only `depth` levels
+/// below the roots are shown.
+pub fn collapsed_below_depth(objects: &[OwnedObject], depth: usize) -> HashSet<Vec<usize>> {
+    fn walk(obj: &OwnedObject, path: &mut Vec<usize>, depth: usize, out: &mut HashSet<Vec<usize>>) {
+        if let Some(children) = obj.children() {
+            if path.len() > depth {
+                out.insert(path.clone());
+            }
+            for (i, child) in children.iter().enumerate() {
+                path.push(i);
+                walk(child, path, depth, out);
+                path.pop();
+            }
+        }
+    }
+    let mut collapsed = HashSet::new();
+    for (i, obj) in objects.iter().enumerate() {
+        walk(obj, &mut vec![i], depth, &mut collapsed);
+    }
+    collapsed
+}
+
+/// Depth of the deepest node with children (top-level nodes are at depth
+/// 0), or `None` when no object has any.
+fn deepest_constructed(objects: &[OwnedObject]) -> Option<usize> {
+    objects
+        .iter()
+        .filter_map(|obj| {
+            let children = obj.children()?;
+            Some(deepest_constructed(children).map_or(0, |d| d + 1))
+        })
+        .max()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::asn1_time::RFC5280_YEAR_PIVOT;
+    use crate::der_parser::parse_owned;
+    use crate::tui::app::AppMode;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use proptest::prelude::*;
+
+    /// A tree row styled as a constructed universal node.
+    fn container_row(label: &str) -> ListItem<'_> {
+        ListItem::new(label).style(Style::default().fg(Color::LightBlue))
+    }
+
+    /// A tree row styled as a character string or OID primitive.
+    fn string_row(label: &str) -> ListItem<'_> {
+        ListItem::new(label).style(Style::default().fg(Color::Green))
+    }
+
+    /// Builds an `App` in its post-parse state from a hex-encoded DER buffer.
+    pub(crate) fn app_from_hex(hex_input: &str) -> App {
+        let bytes = hex::decode(hex_input).unwrap();
+        let mut app = App::new();
+        app.parsed_objects = parse_owned(&bytes).unwrap();
+        app.buffer = bytes;
+        app.selected_path = vec![0];
+        app
+    }
+
+    #[test]
+    fn test_compare_pinned_matches_content_bytes() {
+        // SEQUENCE { INTEGER 5, OCTET STRING 05, INTEGER 6 }
+        let mut app = app_from_hex("3009020105040105020106");
+        app.selected_path = vec![0, 0];
+        app.toggle_pin();
+        assert_eq!(app.pinned_path, Some(vec![0, 0]));
+
+        app.selected_path = vec![0, 1];
+        let comparison = app.compare_pinned().unwrap();
+        assert!(comparison.bytes_equal);
+        assert_eq!(comparison.pinned, "INTEGER (2): 5 (3-bit)");
+        assert_eq!(comparison.selected, "OCTET STRING (4): [5]");
+
+        app.selected_path = vec![0, 2];
+        assert!(!app.compare_pinned().unwrap().bytes_equal);
+    }
+
+    #[test]
+    fn test_primitive_sequence_label_is_flagged() {
+        let app = app_from_hex("10023000");
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions::default(),
+        );
+        assert_eq!(items.len(), 1);
+        let expected =
+            ListItem::new("SEQUENCE (16) [len 2]: [48, 0] ⚠ SEQUENCE encoded as primitive");
+        assert_eq!(items[0], expected);
+    }
+
+    #[test]
+    fn test_validity_times_are_labelled() {
+        let objects = vec![crate::x509::tests::minimal_certificate()];
+        let not_after = |now: &[u8]| {
+            let now = crate::asn1_time::parse_asn1_time(now, true)
+                .unwrap()
+                .unix_seconds();
+            let (items, _) = tui_list_items(
+                &objects,
+                TreeView {
+                    selected_path: &[],
+                    collapsed_nodes: &HashSet::new(),
+                    decode_overrides: &HashMap::new(),
+                    search_query: "",
+                },
+                DisplayOptions {
+                    now,
+                    ..Default::default()
+                },
+            );
+            items[7].clone()
+        };
+        let row = |status: &str| {
+            ListItem::new(format!(
+                "│     └─ GeneralizedTime (24) [len 15] (notAfter): '20500101000000Z' → 2050-01-01T00:00:00Z {}",
+                status
+            ))
+        };
+        assert_eq!(not_after(b"20240101000000Z"), row("(valid)"));
+        assert_eq!(not_after(b"20500101000001Z"), row("(expired)"));
+    }
+
+    #[test]
+    fn test_utc_year_pivot_in_labels() {
+        // UTCTime 490101000000Z
+        let app = app_from_hex("170D3439303130313030303030305A");
+        let label = |utc_year_pivot| {
+            let (items, _) = tui_list_items(
+                &app.parsed_objects,
+                TreeView {
+                    selected_path: &[],
+                    ..app.tree_view()
+                },
+                DisplayOptions {
+                    utc_year_pivot,
+                    ..Default::default()
+                },
+            );
+            items[0].clone()
+        };
+        let row = |year: &str| {
+            ListItem::new(format!(
+                "UTCTime (23) [len 13]: '490101000000Z' → {}-01-01T00:00:00Z",
+                year
+            ))
+        };
+        assert_eq!(label(RFC5280_YEAR_PIVOT), row("2049"));
+        assert_eq!(label(40), row("1949"));
+    }
+
+    #[test]
+    fn test_integer_values_are_signed() {
+        let cases = [
+            ("0201FF", "-1 (1-bit)"),
+            ("0202FF80", "-128 (8-bit)"),
+            ("020180", "-128 (8-bit)"),
+            ("020100", "0 (0-bit)"),
+            ("02017F", "127 (7-bit)"),
+            ("02020080", "128 (8-bit)"),
+            ("0202FF7F", "-129 (8-bit)"),
+            // The 0x00 is required here: without it the value would be -1.
+            ("020200FF", "255 (8-bit)"),
+            // ENUMERATED is encoded like an INTEGER.
+            ("0A0101", "1"),
+            ("0A01FF", "-1"),
+        ];
+        for (input, expected) in cases {
+            let app = app_from_hex(input);
+            assert_eq!(
+                value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+                expected,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_oid_value_display() {
+        let app = app_from_hex("06092A864886F70D01010B");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "1.2.840.113549.1.1.11 (sha256WithRSAEncryption)"
+        );
+        let app = app_from_hex("06032A8001");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "<invalid OID: NonMinimal> [42, 128, 1]"
+        );
+    }
+
+    #[test]
+    fn test_time_value_display_shows_raw_and_iso() {
+        let app = app_from_hex("170D3233303131353132303030305A");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "'230115120000Z' → 2023-01-15T12:00:00Z"
+        );
+        let app = app_from_hex("181332303233303131353134303030302B30323030");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "'20230115140000+0200' → 2023-01-15T12:00:00Z"
+        );
+        let app = app_from_hex("17023939");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "'99' → <invalid time>"
+        );
+    }
+
+    #[test]
+    fn test_boolean_labels() {
+        let cases = [
+            ("010100", "BOOLEAN (1) [len 1]: false"),
+            ("0101FF", "BOOLEAN (1) [len 1]: true"),
+            (
+                "010101",
+                "BOOLEAN (1) [len 1]: true ⚠ non-canonical BOOLEAN 0x01 (DER requires 0x00 or 0xFF)",
+            ),
+        ];
+        for (input, expected) in cases {
+            let app = app_from_hex(input);
+            let (items, _) = tui_list_items(
+                &app.parsed_objects,
+                TreeView {
+                    selected_path: &[],
+                    ..app.tree_view()
+                },
+                DisplayOptions::default(),
+            );
+            assert_eq!(items[0], ListItem::new(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_bit_string_value_display() {
+        let app = app_from_hex("030205A0");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "bits set: {0, 2}, 5 unused: 05A0"
+        );
+        let app = app_from_hex("03020900");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "<invalid BIT STRING> [9, 0]"
+        );
+    }
+
+    #[test]
+    fn test_utf8_string_label() {
+        // SEQUENCE { UTF8String "Grüße" }
+        let app = app_from_hex("30090C074772C3BCC39F65");
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
             },
-            None => return None,
+            DisplayOptions::default(),
+        );
+        assert_eq!(items[1], string_row("└─ UTF8String (12) [len 7]: 'Grüße'"));
+    }
+
+    #[test]
+    fn test_bmp_string_label() {
+        let app = app_from_hex("1E080055007300650072");
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions::default(),
+        );
+        assert_eq!(items[0], string_row("BMPString (30) [len 8]: 'User'"));
+
+        let app = app_from_hex("1E03005500");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "<invalid BMPString>"
+        );
+    }
+
+    #[test]
+    fn test_restricted_string_labels() {
+        let app = app_from_hex("12053132333435");
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions::default(),
+        );
+        assert_eq!(items[0], string_row("NumericString (18) [len 5]: '12345'"));
+
+        let app = app_from_hex("1C08000000480000006B");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "'Hk'"
+        );
+        let app = app_from_hex("1C03000048");
+        assert_eq!(
+            value_display(&app.parsed_objects[0], RFC5280_YEAR_PIVOT),
+            "<invalid UniversalString (28)>"
+        );
+    }
+
+    #[test]
+    fn test_indent_guides() {
+        let indent = Indent::default();
+        assert_eq!(indent_prefix(&[], indent), "");
+        assert_eq!(indent_prefix(&[false], indent), "├─ ");
+        assert_eq!(indent_prefix(&[false, true], indent), "│  └─ ");
+        assert_eq!(indent_prefix(&[true, false], indent), "   ├─ ");
+        assert_eq!(
+            indent_prefix(&[false, true, false], Indent::Spaces(4)),
+            concat!("│    ", "     ", "├─── ")
+        );
+        assert_eq!(indent_prefix(&[true, true], Indent::Spaces(1)), "   └─ ");
+        assert_eq!(indent_prefix(&[true, true], Indent::Tab), "   └─ ");
+
+        // SEQUENCE { SEQUENCE { INTEGER 1, INTEGER 2 }, SEQUENCE { NULL } }, with the
+        // second inner SEQUENCE collapsed.
+        let mut app = app_from_hex("300C300602010102010230020500");
+        app.collapsed_nodes.insert(vec![0, 1]);
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions::default(),
+        );
+        let expected = [
+            container_row("▼ SEQUENCE (16) [len 12]: Constructed (2 children)"),
+            container_row("├─ ▼ SEQUENCE (16) [len 6]: Constructed (2 children)"),
+            ListItem::new("│  ├─ INTEGER (2) [len 1]: 1 (1-bit)"),
+            ListItem::new("│  └─ INTEGER (2) [len 1]: 2 (2-bit)"),
+            container_row("└─ ▶ SEQUENCE (16) [len 2]: Constructed (1 children)"),
+        ];
+        assert_eq!(items, expected.to_vec());
+    }
+
+    #[test]
+    fn test_non_universal_tags_use_brackets() {
+        // SEQUENCE { [0] { INTEGER 2 }, [APPLICATION 2] 05, [PRIVATE 5] 05, universal 0 }
+        let app = app_from_hex("300DA003020102420105C501050000");
+        let objects = &app.parsed_objects;
+        let children = match &objects[0].value {
+            crate::der_parser::OwnedValue::Constructed(children) => children,
+            _ => panic!("expected a SEQUENCE"),
         };
+        let tags: Vec<String> = children.iter().map(tag_display).collect();
+        assert_eq!(tags, ["[0]", "[APPLICATION 2]", "[PRIVATE 5]", "0"]);
+    }
+
+    #[test]
+    fn test_labels_show_length() {
+        // SEQUENCE with a long-form length around a 200-byte OCTET STRING.
+        let app = app_from_hex(&format!("3081CB0481C8{}", "00".repeat(200)));
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions {
+                value_width: 4,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            items[0],
+            container_row("▼ SEQUENCE (16) [len 203]: Constructed (1 children)")
+        );
+        assert_eq!(
+            items[1],
+            ListItem::new("└─ OCTET STRING (4) [len 200]: [0,…")
+        );
+    }
+
+    #[test]
+    fn test_rdn_set_label_follows_toggle() {
+        // SEQUENCE { SET { SEQUENCE { OID commonName, UTF8String "example.com" } } }
+        let mut app = app_from_hex("30163114301206035504030C0B6578616D706C652E636F6D");
+        app.mode = AppMode::View;
+        fn label(app: &App) -> ListItem<'_> {
+            let (items, _) = tui_list_items(
+                &app.parsed_objects,
+                TreeView {
+                    selected_path: &[],
+                    ..app.tree_view()
+                },
+                app.display_options(),
+            );
+            items[1].clone()
+        }
+        assert_eq!(
+            label(&app),
+            container_row("└─ ▼ SET (17) [len 20]: CN=example.com")
+        );
+        app.handle_input(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert_eq!(
+            label(&app),
+            container_row("└─ ▼ SET (17) [len 20]: Constructed (1 children)")
+        );
+    }
+
+    #[test]
+    fn test_algorithm_identifier_label_follows_toggle() {
+        // SEQUENCE { SEQUENCE { OID ecdsa-with-SHA256 }, BIT STRING }
+        let mut app = app_from_hex("300F300A06082A8648CE3D040302030100");
+        app.mode = AppMode::View;
+        fn label(app: &App) -> ListItem<'_> {
+            let (items, _) = tui_list_items(
+                &app.parsed_objects,
+                TreeView {
+                    selected_path: &[],
+                    ..app.tree_view()
+                },
+                app.display_options(),
+            );
+            items[1].clone()
+        }
+        assert_eq!(
+            label(&app),
+            container_row(
+                "├─ ▼ SEQUENCE (16) [len 10] (AlgorithmIdentifier: ecdsa-with-SHA256): Constructed (1 children)"
+            )
+        );
+        app.handle_input(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert_eq!(
+            label(&app),
+            container_row("├─ ▼ SEQUENCE (16) [len 10]: Constructed (1 children)")
+        );
+    }
+
+    #[test]
+    fn test_certificate_fields_named_by_schema() {
+        // Certificate { tbsCertificate { [0] { INTEGER 2 }, INTEGER 1 },
+        //               SEQUENCE {}, BIT STRING }
+        let app = app_from_hex("300F3008A0030201020201013000030100");
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            app.display_options(),
+        );
+        assert_eq!(
+            items[1],
+            container_row("├─ ▼ SEQUENCE (16) [len 8] (tbsCertificate): Constructed (2 children)")
+        );
+        assert_eq!(
+            items[4],
+            ListItem::new("│  └─ INTEGER (2) [len 1] (serialNumber): 1 (1-bit)")
+        );
+        assert_eq!(
+            items[6],
+            ListItem::new("└─ BIT STRING (3) [len 1] (signatureValue): bits set: {}, 0 unused: 00")
+        );
+    }
+
+    #[test]
+    fn test_raw_values_when_pretty_is_off() {
+        // SEQUENCE { PrintableString "AB" }
+        let mut app = app_from_hex("300413024142");
+        app.mode = AppMode::View;
+        app.handle_input(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        assert!(!app.pretty);
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            app.display_options(),
+        );
+        assert_eq!(
+            items[1],
+            string_row("└─ PrintableString (19) [len 2]: [41, 42]")
+        );
+    }
+
+    #[test]
+    fn test_node_style_by_tag_class() {
+        // [0] { INTEGER 2 }, SEQUENCE {}, UTF8String "a", INTEGER 1
+        let app = app_from_hex("A00302010230000C0161020101");
+        let objects = &app.parsed_objects;
+        assert_eq!(
+            node_style(&objects[0], false, false),
+            Style::default().fg(Color::Cyan)
+        );
+        assert_eq!(
+            node_style(&objects[1], false, false),
+            Style::default().fg(Color::LightBlue)
+        );
+        assert_eq!(
+            node_style(&objects[2], false, false),
+            Style::default().fg(Color::Green)
+        );
+        assert_eq!(node_style(&objects[3], false, false), Style::default());
+        // Selection and search highlighting take precedence.
+        assert_eq!(
+            node_style(&objects[0], true, true),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            node_style(&objects[0], false, true),
+            Style::default().fg(Color::Magenta)
+        );
+    }
+
+    #[test]
+    fn test_clamp_selection() {
+        // SEQUENCE { INTEGER 1, SEQUENCE { INTEGER 2 } }, NULL
+        let mut app = app_from_hex("300802010130030201020500");
+        app.selected_path = vec![0, 5, 3];
+        app.clamp_selection();
+        assert_eq!(app.selected_path, vec![0, 1, 0]);
+
+        app.selected_path = vec![0, 0, 2];
+        app.clamp_selection();
+        assert_eq!(app.selected_path, vec![0, 0]);
+
+        app.selected_path = vec![3, 1];
+        app.clamp_selection();
+        assert_eq!(app.selected_path, vec![1]);
+
+        app.selected_path = vec![];
+        app.clamp_selection();
+        assert_eq!(app.selected_path, vec![0]);
+
+        app.parsed_objects.clear();
+        app.selected_path = vec![0, 1];
+        app.clamp_selection();
+        assert!(app.selected_path.is_empty());
+    }
+
+    #[test]
+    fn test_selection_status() {
+        // SEQUENCE { INTEGER 1, [0] { OCTET STRING 00, UTF8String "a" } }
+        let mut app = app_from_hex("300B020101A0060401000C0161");
+        app.selected_path = vec![0, 1, 1];
+        assert_eq!(
+            app.selected_status().as_deref(),
+            Some("0 > 1 > 1 │ Universal 12 UTF8String │ len 1 │ bytes 10..13")
+        );
+        app.selected_path = vec![0, 1];
+        assert_eq!(
+            app.selected_status().as_deref(),
+            Some("0 > 1 │ ContextSpecific 0 │ len 6 │ bytes 5..13")
+        );
+        app.delete_selected();
+        assert_eq!(
+            app.selected_status().as_deref(),
+            Some("0 > 0 │ Universal 2 INTEGER │ len 1 │ bytes 2..5")
+        );
+        app.selected_path = vec![0];
+        assert!(
+            app.selected_status()
+                .unwrap()
+                .ends_with("len 3 │ bytes: edited")
+        );
+    }
+
+    #[test]
+    fn test_long_values_are_truncated() {
+        assert_eq!(truncate_preview("abcdef", 6), "abcdef");
+        assert_eq!(truncate_preview("abcdefg", 6), "abcde…");
+        assert_eq!(truncate_preview("ÄÖÜäöü", 4), "ÄÖÜ…");
+
+        // OCTET STRING with 32 bytes, whose debug dump is far wider than 40.
+        let app = app_from_hex(&format!("0420{}", "AB".repeat(32)));
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions {
+                value_width: 40,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            items[0],
+            ListItem::new("OCTET STRING (4) [len 32]: [171, 171, 171, 171, 171, 171, 171, 171…")
+        );
+
+        // Strings keep their text, cut to the same width.
+        let app = app_from_hex(&format!("0C32{}", "61".repeat(50)));
+        let (items, _) = tui_list_items(
+            &app.parsed_objects,
+            TreeView {
+                selected_path: &[],
+                ..app.tree_view()
+            },
+            DisplayOptions {
+                value_width: 10,
+                ..Default::default()
+            },
+        );
+        assert_eq!(items[0], string_row("UTF8String (12) [len 50]: 'aaaaaaaa…"));
+    }
+
+    #[test]
+    fn test_toggle_pin_twice_unpins() {
+        let mut app = app_from_hex("3003020105");
+        app.toggle_pin();
+        app.toggle_pin();
+        assert_eq!(app.pinned_path, None);
+        assert_eq!(app.compare_pinned(), None);
+    }
+
+    /// SEQUENCE { SEQUENCE { SEQUENCE { NULL } }, NULL }
+    const DEEP: &str = "30083004300205000500";
+
+    #[test]
+    fn test_fold_depth_one_collapses_below_top_level() {
+        let mut app = app_from_hex(DEEP);
+        app.selected_path = vec![0, 0, 0, 0];
+        app.set_fold_depth(Some(1));
+        assert_eq!(
+            app.collapsed_nodes,
+            HashSet::from([vec![0, 0], vec![0, 0, 0]])
+        );
+        assert_eq!(app.selected_path, vec![0, 0]);
+        assert!(app.tree_title().contains("[fold 1]"));
+    }
+
+    #[test]
+    fn test_fold_keys_step_through_depths() {
+        let mut app = app_from_hex(DEEP);
+        app.mode = AppMode::View;
+        let mut press = |c| {
+            app.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+            (app.fold_depth, app.collapsed_nodes.len())
+        };
+        assert_eq!(press('-'), (Some(2), 1));
+        assert_eq!(press('-'), (Some(1), 2));
+        assert_eq!(press('-'), (Some(0), 3));
+        assert_eq!(press('-'), (Some(0), 3));
+        assert_eq!(press('+'), (Some(1), 2));
+        assert_eq!(press('+'), (Some(2), 1));
+        assert_eq!(press('+'), (None, 0));
+        assert_eq!(press('+'), (None, 0));
+    }
+
+    /// Up to 500 presses of the tree navigation keys.
+    fn navigation_keys() -> impl Strategy<Value = Vec<KeyCode>> {
+        let keys = vec![
+            KeyCode::Char('j'),
+            KeyCode::Char('k'),
+            KeyCode::Char('h'),
+            KeyCode::Char('l'),
+            KeyCode::Char('g'),
+            KeyCode::Char('G'),
+            KeyCode::Char('-'),
+            KeyCode::Char('+'),
+            KeyCode::PageDown,
+            KeyCode::PageUp,
+        ];
+        prop::collection::vec(prop::sample::select(keys), 0..500)
+    }
+
+    /// Presses `keys` and checks after each one that the selection still
+    /// names a node (or is empty when there are no objects).
+    fn press_navigation_keys(mut app: App, tree_height: usize, keys: &[KeyCode]) {
+        app.mode = AppMode::View;
+        app.tree_height = tree_height;
+        for &key in keys {
+            app.handle_input(KeyEvent::new(key, KeyModifiers::NONE));
+            assert_eq!(
+                app.get_selected_object().is_some(),
+                !app.parsed_objects.is_empty(),
+                "after {:?}: {:?}",
+                key,
+                app.selected_path
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_navigation_on_empty_tree_is_a_no_op(
+            height in 0usize..6,
+            keys in navigation_keys(),
+        ) {
+            let mut app = App::new();
+            app.mode = AppMode::View;
+            app.tree_height = height;
+            for key in [KeyCode::Char('j'), KeyCode::Char('k'), KeyCode::PageDown] {
+                app.handle_input(KeyEvent::new(key, KeyModifiers::NONE));
+            }
+            app.handle_input(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE));
+            app.handle_input(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE));
+            prop_assert!(app.selected_path.is_empty());
+            prop_assert_eq!(app.tree_scroll, 0);
+            press_navigation_keys(app, height, &keys);
+        }
+
+        #[test]
+        fn test_navigation_on_tiny_trees(
+            // A single primitive, a single empty SEQUENCE, and a SEQUENCE
+            // holding an empty SET next to a NULL.
+            hex_input in prop::sample::select(vec!["0500", "3000", "300431000500"]),
+            height in 0usize..6,
+            keys in navigation_keys(),
+        ) {
+            press_navigation_keys(app_from_hex(hex_input), height, &keys);
+        }
+    }
+
+    #[test]
+    fn test_navigation_recovers_from_stale_selection() {
+        let mut app = app_from_hex("3000");
+        app.mode = AppMode::View;
+        app.selected_path = vec![3, 1];
+        app.handle_input(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+        assert_eq!(app.selected_path, vec![0]);
+        app.selected_path.clear();
+        app.handle_input(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(app.selected_path, vec![0]);
+    }
+
+    fn press_view_key(app: &mut App, code: KeyCode) {
+        app.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    /// SEQUENCE { SEQUENCE { NULL }, NULL }
+    const TWO_LEVELS: &str = "3006300205000500";
+
+    #[test]
+    fn test_l_expands_collapsed_node() {
+        let mut app = app_from_hex(TWO_LEVELS);
+        app.mode = AppMode::View;
+        app.collapsed_nodes.insert(vec![0]);
+        press_view_key(&mut app, KeyCode::Char('l'));
+        assert!(app.collapsed_nodes.is_empty());
+        assert_eq!(app.selected_path, vec![0]);
+    }
+
+    #[test]
+    fn test_l_descends_into_expanded_node() {
+        let mut app = app_from_hex(TWO_LEVELS);
+        app.mode = AppMode::View;
+        press_view_key(&mut app, KeyCode::Right);
+        assert_eq!(app.selected_path, vec![0, 0]);
+        press_view_key(&mut app, KeyCode::Right);
+        assert_eq!(app.selected_path, vec![0, 0, 0]);
+        // A primitive has nowhere to go.
+        press_view_key(&mut app, KeyCode::Char('l'));
+        assert_eq!(app.selected_path, vec![0, 0, 0]);
+        assert!(app.collapsed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_h_collapses_expanded_node() {
+        let mut app = app_from_hex(TWO_LEVELS);
+        app.mode = AppMode::View;
+        app.selected_path = vec![0, 0];
+        press_view_key(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.collapsed_nodes, HashSet::from([vec![0, 0]]));
+        assert_eq!(app.selected_path, vec![0, 0]);
+    }
+
+    #[test]
+    fn test_h_ascends_from_collapsed_node() {
+        let mut app = app_from_hex(TWO_LEVELS);
+        app.mode = AppMode::View;
+        app.selected_path = vec![0, 0];
+        app.collapsed_nodes.insert(vec![0, 0]);
+        press_view_key(&mut app, KeyCode::Left);
+        assert_eq!(app.selected_path, vec![0]);
+        assert_eq!(app.collapsed_nodes, HashSet::from([vec![0, 0]]));
+        // Primitives go straight to their parent too, and a collapsed
+        // top-level node stays put.
+        app.selected_path = vec![0, 1];
+        press_view_key(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.selected_path, vec![0]);
+        press_view_key(&mut app, KeyCode::Char('h'));
+        press_view_key(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.selected_path, vec![0]);
+        assert!(app.collapsed_nodes.contains(&vec![0]));
+    }
+
+    #[test]
+    fn test_single_child_explicit_tag_is_inlined() {
+        // SEQUENCE { [0] { INTEGER 2 }, NULL }
+        let mut app = app_from_hex("3007A0030201020500");
+        app.mode = AppMode::View;
+        press_view_key(&mut app, KeyCode::Char('I'));
+        fn rows(app: &App) -> (Vec<ListItem<'_>>, usize) {
+            tui_list_items(&app.parsed_objects, app.tree_view(), app.display_options())
+        }
+        let (items, _) = rows(&app);
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[1],
+            ListItem::new("├─ ▼ [0] [len 3] INTEGER (2) [len 1]: 2 (2-bit)")
+                .style(Style::default().fg(Color::Cyan))
+        );
+
+        // The wrapper and its child are one row: `l` stays put and `j` skips
+        // the child.
+        press_view_key(&mut app, KeyCode::Char('j'));
+        press_view_key(&mut app, KeyCode::Char('l'));
+        assert_eq!(app.selected_path, vec![0, 0]);
+        press_view_key(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.selected_path, vec![0, 1]);
+        press_view_key(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.selected_path, vec![0, 0]);
+
+        // Selecting the hidden child (e.g. by a search) highlights the row.
+        app.selected_path = vec![0, 0, 0];
+        assert_eq!(rows(&app).1, 1);
+
+        // Turning the toggle off gives the child its row back.
+        press_view_key(&mut app, KeyCode::Char('I'));
+        assert_eq!(rows(&app).0.len(), 4);
     }
-    current
 }
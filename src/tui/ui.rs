@@ -1,7 +1,7 @@
 // src/tui/ui.rs
-use crate::tui::app::App;
+use crate::tui::app::{App, COPY_CONFIRMATION_TIME, ClipboardFormat};
 use crate::tui::tree::tui_list_items;
-use clipboard::{ClipboardContext, ClipboardProvider};
+use base64::Engine;
 use ratatui::layout::Alignment;
 use ratatui::widgets::BorderType;
 use ratatui::widgets::Clear;
@@ -12,26 +12,111 @@ use ratatui::{
     widgets::*,
 };
 
+const LOG_PANE_HEIGHT: u16 = 6;
+/// Height of the `X` buffer hex panel, including its borders.
+const BUFFER_HEX_HEIGHT: u16 = 10;
+
 impl App {
-    pub fn draw(&self, f: &mut Frame) {
+    pub fn draw(&mut self, f: &mut Frame) {
         let is_input_mode = matches!(self.mode, crate::tui::app::AppMode::Input);
         let constraints = if is_input_mode {
             [Constraint::Min(10), Constraint::Length(8)] // Large input, small tree
         } else {
             [Constraint::Length(3), Constraint::Min(10)] // Small input, large tree
         };
+        let main_area = if self.show_log {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(13), Constraint::Length(LOG_PANE_HEIGHT)])
+                .split(f.area());
+            self.draw_log(f, split[1]);
+            split[0]
+        } else {
+            f.area()
+        };
+        let main_area = match &self.last_error {
+            Some(error) => {
+                let split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(main_area);
+                let status = Paragraph::new(format!("Error: {}", error))
+                    .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+                f.render_widget(status, split[1]);
+                split[0]
+            }
+            None => main_area,
+        };
+        let status = if self.copied_tree {
+            Some("Copied tree".to_string())
+        } else {
+            self.selected_status()
+        };
+        let main_area = match status.filter(|_| !is_input_mode) {
+            Some(status) => {
+                let split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(main_area);
+                let fg = if self.copied_tree {
+                    Color::Yellow
+                } else {
+                    Color::Black
+                };
+                let bar = Paragraph::new(status).style(Style::default().fg(fg).bg(Color::Gray));
+                f.render_widget(bar, split[1]);
+                split[0]
+            }
+            None => main_area,
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(constraints)
-            .split(f.area());
+            .split(main_area);
 
         self.draw_input(f, chunks[0]);
-        self.draw_tree(f, chunks[1]);
+        if self.show_buffer_hex && !is_input_mode {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Length(BUFFER_HEX_HEIGHT)])
+                .split(chunks[1]);
+            self.draw_tree(f, split[0]);
+            self.draw_buffer_hex(f, split[1]);
+        } else {
+            self.draw_tree(f, chunks[1]);
+        }
 
         if self.show_help {
             self.draw_help_modal(f);
+        } else if self.export_active {
+            self.draw_export_prompt(f);
+        } else if self.offset_active {
+            self.draw_prompt(
+                f,
+                "Jump to byte offset (decimal or 0x hex)",
+                &self.offset_buffer,
+            );
+        } else if self.edit_active {
+            self.draw_edit_prompt(f);
+        } else if self.decode_as_active {
+            self.draw_prompt(
+                f,
+                "Decode as universal type (name or number, empty to reset)",
+                &self.decode_as_buffer,
+            );
+        } else if self.session_active {
+            let title = if self.session_save {
+                "Save session to path"
+            } else {
+                "Load session from path"
+            };
+            self.draw_prompt(f, title, &self.session_buffer);
         } else if self.should_show_hex_modal() {
             self.draw_hex_modal(f);
+        } else if self.show_compare_modal {
+            self.draw_compare_modal(f);
+        } else if self.show_validation_modal {
+            self.draw_validation_modal(f);
         } else {
             self.draw_help_hint(f);
         }
@@ -48,34 +133,76 @@ impl App {
         let active_style = Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD);
-        let title = if is_active {
+        let mut title = vec![if is_active {
             Span::styled("Input", active_style)
         } else {
             Span::raw("Input")
-        };
+        }];
+        if let Some(err) = &self.input_error {
+            title.push(Span::styled(
+                format!(" — {}", err),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let title = Line::from(title);
 
-        let paragraph = Paragraph::new(self.input_buffer.as_str())
+        let cursor = is_active.then(|| self.input_cursor());
+        let paragraph = Paragraph::new(input_lines(&self.input_buffer, cursor))
             .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(Wrap { trim: false });
         f.render_widget(paragraph, area);
     }
 
-    pub fn draw_tree(&self, f: &mut Frame, area: Rect) {
+    /// The tree panel title: the search query while one is active, the fold
+    /// depth when folding is on, and a guess at the document type when the
+    /// outer structure is recognized.
+    pub fn tree_title(&self) -> String {
+        let mut title = "ASN.1 Tree View".to_string();
+        if let Some(file) = self.files.get(self.active_file) {
+            title.push_str(&format!(
+                " — {} ({}/{})",
+                file.name,
+                self.active_file + 1,
+                self.files.len()
+            ));
+        }
+        if self.search_active || !self.search_buffer.is_empty() {
+            title.push_str(&format!(" /{}", self.search_buffer));
+        }
+        if let Some(depth) = self.fold_depth {
+            title.push_str(&format!(" [fold {}]", depth));
+        }
+        if let Some(kind) = crate::x509::document_kind(&self.parsed_objects) {
+            title.push_str(&format!(" — Looks like: {}", kind));
+        }
+        title
+    }
+
+    pub fn draw_tree(&mut self, f: &mut Frame, area: Rect) {
         let is_active = matches!(self.mode, crate::tui::app::AppMode::View);
         let active_style = Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD);
+        let title = self.tree_title();
         let title = if is_active {
-            Span::styled("ASN.1 Tree View", active_style)
+            Span::styled(title, active_style)
         } else {
-            Span::raw("ASN.1 Tree View")
+            Span::raw(title)
         };
-        let (items, selected_idx) = tui_list_items(
+        let (mut items, selected_idx) = tui_list_items(
             &self.parsed_objects,
-            &self.selected_path,
-            &self.collapsed_nodes,
+            self.tree_view(),
+            self.display_options(),
         );
-        let height = area.height as usize;
+        if let Some(error) = &self.parse_stop {
+            items.push(
+                ListItem::new(format!("⚠ parsing stopped: {}", error))
+                    .style(Style::default().fg(Color::Red)),
+            );
+        }
+        // Rows inside the borders; remembered so paging matches the screen.
+        let height = area.height.saturating_sub(2) as usize;
+        self.tree_height = height;
         let total_items = items.len();
         let mut scroll = self.tree_scroll;
         // Ensure scroll is always valid and the selected item is visible
@@ -89,6 +216,7 @@ impl App {
         if scroll + height > total_items {
             scroll = total_items.saturating_sub(height);
         }
+        self.tree_scroll = scroll;
         let end = (scroll + height).min(total_items);
         let visible_items = items[scroll..end].to_vec();
         let list =
@@ -96,6 +224,24 @@ impl App {
         f.render_widget(list, area);
     }
 
+    /// Draws the message pane, newest entries at the bottom, offset by `log_scroll`.
+    pub fn draw_log(&self, f: &mut Frame, area: Rect) {
+        let visible = area.height.saturating_sub(2) as usize;
+        let end = self.messages.len().saturating_sub(self.log_scroll);
+        let start = end.saturating_sub(visible);
+        let lines: Vec<Line> = self.messages[start..end]
+            .iter()
+            .map(|m| Line::from(m.display_line()))
+            .collect();
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Messages")
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(paragraph, area);
+    }
+
     pub fn draw_help_modal(&self, f: &mut Frame) {
         let area = centered_rect(60, 60, f.area());
         let help_text = vec![
@@ -104,26 +250,58 @@ impl App {
             "General:",
             "  q         Quit",
             "  ?         Show this help",
+            "  Ctrl-S    Save session (input, folds, selection)",
+            "  Ctrl-O    Load a saved session",
             "",
             "Input Mode:",
             "  Ctrl-R    Parse input",
+            "  Ctrl-P    Parse into the preview, staying in Input",
             "  Ctrl-U    Clear input",
             "  Tab/Esc   Switch to View",
             "  Enter     Newline",
-            "  Any char  Add to buffer",
+            "  Any char  Insert at cursor",
+            "  ←/→       Move cursor",
+            "  Home/End  Start/end of line",
+            "  Bksp/Del  Delete before/at cursor",
             "",
             "View Mode:",
             "  i         Switch to Input",
             "  Tab       Switch to Input",
             "  j/k       Down/Up (navigate)",
-            "  h/l       Collapse/Expand node",
-            "  d         Delete node (not implemented)",
-            "  a         Add child (not implemented)",
+            "  PgUp/PgDn Page up/down",
+            "  gg/G      Jump to first/last node",
+            "  [/]       Previous/next file",
+            "  h/Left    Collapse node, or go to parent",
+            "  l/Right   Expand node, or go to first child",
+            "  -/+       Fold one level shallower/deeper",
+            "  d         Delete node",
+            "  a         Add child (or sibling after a primitive)",
             "  x         Show hex modal for selected item",
-            "  Esc       Close hex modal",
+            "  X         Toggle hex panel of the whole buffer",
+            "  t         Toggle CN=… and algorithm labels",
+            "  p         Toggle decoded/raw hex values",
+            "  I         Toggle single-child wrappers on one row",
+            "  o or :    Jump to the node at a byte offset",
+            "  Y         Copy the whole tree as text",
+            "  E/Enter   Parse OCTET/BIT STRING contents as DER",
+            "  m         Pin/unpin selected node",
+            "  c         Compare pinned and selected nodes",
+            "  v         Show DER validation issues",
+            "  e         Edit selected primitive, else export PEM",
+            "  D         Decode selected primitive as another type",
+            "  w         Export tree as PEM to a file (anywhere)",
+            "  W         Export tree as raw DER to a file",
+            "  /         Search tags and values",
+            "  n/N       Next/previous match",
+            "  L         Toggle message pane",
+            "  J/K       Scroll message pane",
+            "  Esc       Close hex/compare/validation modal",
             "",
             "Hex Modal:",
-            "  Ctrl-C    Copy hex to clipboard",
+            "  j/k       Scroll one row",
+            "  PgUp/PgDn Scroll one page",
+            "  Ctrl-C    Copy exact TLV bytes as hex",
+            "  Ctrl-B    Copy exact TLV bytes as base64",
             "  Esc       Close hex modal",
             "",
             "Press any key to close this help.",
@@ -151,59 +329,29 @@ impl App {
         let Some(obj) = self.get_selected_object() else {
             return;
         };
-        let (tag_bytes, length_bytes, value_bytes) = get_tag_length_value_bytes(obj);
-        let mut copied = false;
-        // Compose colored spans
-        let mut spans = vec![];
-        if !tag_bytes.is_empty() {
-            let tag_hex = tag_bytes
-                .iter()
-                .map(|b| format!("{:02X}", b))
-                .collect::<Vec<_>>()
-                .join(" ");
-            spans.push(Span::styled(tag_hex, Style::default().fg(Color::Cyan)));
-        }
-        if !length_bytes.is_empty() {
-            if !spans.is_empty() {
-                spans.push(Span::raw(" "));
-            }
-            let len_hex = length_bytes
-                .iter()
-                .map(|b| format!("{:02X}", b))
-                .collect::<Vec<_>>()
-                .join(" ");
-            spans.push(Span::styled(len_hex, Style::default().fg(Color::White)));
-        }
-        if !value_bytes.is_empty() {
-            if !spans.is_empty() {
-                spans.push(Span::raw(" "));
-            }
-            let val_hex = value_bytes
-                .iter()
-                .map(|b| format!("{:02X}", b))
-                .collect::<Vec<_>>()
-                .join(" ");
-            spans.push(Span::styled(val_hex, Style::default().fg(Color::Green)));
-        }
-        if self.copy_hex_to_clipboard {
-            let all_bytes = tag_bytes
-                .iter()
-                .chain(length_bytes.iter())
-                .chain(value_bytes.iter())
-                .map(|b| format!("{:02X}", b))
-                .collect::<Vec<_>>()
-                .join(" ");
-            if let Ok(mut ctx) = ClipboardContext::new() {
-                let _ = ctx.set_contents(all_bytes);
-                copied = true;
-            }
-        }
-        let mut lines = vec![Line::from(spans)];
-        if copied {
-            lines.push(Line::from(vec![Span::styled(
-                "Copied to clipboard!",
-                Style::default().fg(Color::Yellow),
-            )]));
+        let (tag_bytes, length_bytes, value_bytes) = self
+            .original_tlv_bytes(obj)
+            .unwrap_or_else(|| crate::der_encoder::tlv_bytes(obj));
+        let base_offset = if self.original_tlv_bytes(obj).is_some() {
+            obj.tlv_start
+        } else {
+            0
+        };
+        let rows = hex_dump_lines(&tag_bytes, &length_bytes, &value_bytes, base_offset);
+        let visible = area.height.saturating_sub(2) as usize;
+        let scroll = self.hex_scroll.min(rows.len().saturating_sub(visible));
+        let mut lines: Vec<Line> = rows.into_iter().skip(scroll).collect();
+        if self
+            .copy_confirmation
+            .is_some_and(|at| at.elapsed() < COPY_CONFIRMATION_TIME)
+        {
+            lines.insert(
+                0,
+                Line::from(vec![Span::styled(
+                    "Copied to clipboard!",
+                    Style::default().fg(Color::Yellow),
+                )]),
+            );
         }
         let paragraph = Paragraph::new(lines).block(
             Block::default()
@@ -215,6 +363,159 @@ impl App {
         f.render_widget(paragraph, area);
     }
 
+    pub fn draw_compare_modal(&self, f: &mut Frame) {
+        let area = centered_rect(70, 30, f.area());
+        let Some(comparison) = self.compare_pinned() else {
+            return;
+        };
+        let (verdict, verdict_color) = if comparison.bytes_equal {
+            ("Bytes match", Color::Green)
+        } else {
+            ("Bytes differ", Color::Red)
+        };
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Pinned:   ", Style::default().fg(Color::Cyan)),
+                Span::raw(comparison.pinned),
+            ]),
+            Line::from(vec![
+                Span::styled("Selected: ", Style::default().fg(Color::Cyan)),
+                Span::raw(comparison.selected),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                verdict,
+                Style::default()
+                    .fg(verdict_color)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ];
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Compare")
+                    .border_type(BorderType::Double),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    /// Draws a single-line text prompt centered on the screen.
+    fn draw_prompt(&self, f: &mut Frame, title: &str, text: &str) {
+        let area = centered_rect(60, 20, f.area());
+        let area = Rect {
+            y: area.y + area.height.saturating_sub(3) / 2,
+            height: 3.min(area.height),
+            ..area
+        };
+        let paragraph = Paragraph::new(format!("{}_", text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_type(BorderType::Double),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    /// Single-line prompt for the path the `w` key exports PEM to, or `W`
+    /// raw DER.
+    fn draw_export_prompt(&self, f: &mut Frame) {
+        let title = if self.export_der {
+            "Export DER to path".to_string()
+        } else {
+            format!("Export PEM ({}) to path", self.pem_label)
+        };
+        self.draw_prompt(f, &title, &self.export_buffer);
+    }
+
+    /// Single-line prompt for the new value of the selected primitive (`e`).
+    fn draw_edit_prompt(&self, f: &mut Frame) {
+        let title = match self.get_selected_object() {
+            Some(obj) => format!("Edit {}", crate::tui::tree::tag_display(obj)),
+            None => "Edit value".to_string(),
+        };
+        self.draw_prompt(f, &title, &self.edit_buffer);
+    }
+
+    /// Lists every DER canonical-form violation in the parsed tree.
+    pub fn draw_validation_modal(&self, f: &mut Frame) {
+        let area = centered_rect(70, 50, f.area());
+        let violations = crate::validate::validate_der(&self.parsed_objects);
+        let lines: Vec<Line> = if violations.is_empty() {
+            vec![Line::from(Span::styled(
+                "No DER violations found",
+                Style::default().fg(Color::Green),
+            ))]
+        } else {
+            violations
+                .iter()
+                .map(|v| {
+                    Line::from(vec![
+                        Span::styled(format!("{:?} ", v.path), Style::default().fg(Color::Cyan)),
+                        Span::raw(v.message.clone()),
+                    ])
+                })
+                .collect()
+        };
+        let title = format!("DER Validation ({} issue(s))", violations.len());
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_type(BorderType::Double),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    /// Dumps the whole input buffer, scrolled to and highlighting the bytes
+    /// of the selected node.
+    fn draw_buffer_hex(&self, f: &mut Frame, area: Rect) {
+        let highlight = self.selected_buffer_range();
+        let title = match &highlight {
+            Some(range) => format!(
+                "Buffer ({} bytes) — {}..{}",
+                self.buffer.len(),
+                range.start,
+                range.end
+            ),
+            None => format!("Buffer ({} bytes)", self.buffer.len()),
+        };
+        let first_row = highlight.as_ref().map_or(0, |r| r.start / HEX_ROW_BYTES);
+        let visible = area.height.saturating_sub(2) as usize;
+        let total_rows = self.buffer.len().div_ceil(HEX_ROW_BYTES);
+        let scroll = first_row.min(total_rows.saturating_sub(visible));
+        let rows = buffer_dump_lines(&self.buffer, highlight, scroll..scroll + visible);
+        let paragraph =
+            Paragraph::new(rows).block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(paragraph, area);
+    }
+
+    /// Slices the object's exact encoding out of the source buffer using the
+    /// offsets recorded at parse time.
+    fn original_tlv_bytes(
+        &self,
+        obj: &crate::der_parser::OwnedObject,
+    ) -> Option<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let encoded = self.buffer.get(obj.tlv_start..obj.tlv_end)?;
+        let (tag, length, value) = crate::der_parser::split_tlv(encoded)?;
+        Some((tag.to_vec(), length.to_vec(), value.to_vec()))
+    }
+
+    /// The object's encoding exactly as it appears in the source buffer, or its
+    /// re-encoding when it was edited or has no recorded offsets.
+    pub(crate) fn selected_tlv_bytes(&self, obj: &crate::der_parser::OwnedObject) -> Vec<u8> {
+        match self.original_tlv_bytes(obj) {
+            Some(_) => self.buffer[obj.tlv_start..obj.tlv_end].to_vec(),
+            None => crate::der_encoder::to_der(obj),
+        }
+    }
+
     /// Draws a small help hint in the bottom right corner.
     fn draw_help_hint(&self, f: &mut Frame) {
         let area = f.area();
@@ -242,87 +543,143 @@ impl App {
     }
 }
 
-fn get_object_hex_recursive(obj: &crate::der_parser::OwnedObject) -> Vec<u8> {
-    match &obj.value {
-        crate::der_parser::OwnedValue::Primitive(bytes) => bytes.clone(),
-        crate::der_parser::OwnedValue::Constructed(children) => {
-            let mut out = Vec::new();
-            // Add this object's own bytes if available (if you want to include tag/length, you may need to store them)
-            for child in children {
-                out.extend(get_object_hex_recursive(child));
-            }
-            out
-        }
-    }
+/// Bytes shown per row of the hex dump.
+pub const HEX_ROW_BYTES: usize = 16;
+
+/// Splits the input text into lines, drawing the cell at byte offset
+/// `cursor` reversed. A cursor at the end of a line is drawn as a reversed
+/// space so it stays visible; the paragraph's wrapping then places it.
+pub fn input_lines(buffer: &str, cursor: Option<usize>) -> Vec<Line<'static>> {
+    let mut start = 0;
+    buffer
+        .split('\n')
+        .map(|line| {
+            let end = start + line.len();
+            let at = cursor
+                .filter(|&c| (start..=end).contains(&c))
+                .map(|c| c - start);
+            start = end + 1;
+            let Some(at) = at else {
+                return Line::from(line.to_string());
+            };
+            let under = line[at..].chars().next();
+            let after = at + under.map_or(0, char::len_utf8);
+            Line::from(vec![
+                Span::raw(line[..at].to_string()),
+                Span::styled(
+                    under.unwrap_or(' ').to_string(),
+                    Style::default().add_modifier(Modifier::REVERSED),
+                ),
+                Span::raw(line[after..].to_string()),
+            ])
+        })
+        .collect()
 }
 
-/// Extracts the tag, length, and value bytes for a single ASN.1 object.
-fn get_tag_length_value_bytes(obj: &crate::der_parser::OwnedObject) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
-    // This assumes the object was parsed from DER and the tag/length/value are contiguous in the original encoding.
-    // If you have the original DER bytes, you should store them per object for perfect accuracy.
-    // Here, we reconstruct them as best as possible from the object fields.
-    use crate::der_parser::OwnedValue;
-    let mut tag_bytes = vec![];
-    let mut length_bytes = vec![];
-    let mut value_bytes = vec![];
-    // Tag encoding (single byte for most tags)
-    let tag = &obj.tag;
-    let mut first_byte = ((match tag.class {
-        crate::der_parser::TagClass::Universal => 0b00,
-        crate::der_parser::TagClass::Application => 0b01,
-        crate::der_parser::TagClass::ContextSpecific => 0b10,
-        crate::der_parser::TagClass::Private => 0b11,
-    }) << 6) as u8;
-    if tag.constructed {
-        first_byte |= 0b0010_0000;
-    }
-    if tag.number < 31 {
-        first_byte |= tag.number as u8;
-        tag_bytes.push(first_byte);
-    } else {
-        first_byte |= 0b0001_1111;
-        tag_bytes.push(first_byte);
-        let mut n = tag.number;
-        let mut stack = vec![];
-        while n > 0 {
-            stack.push((n & 0x7F) as u8);
-            n >>= 7;
-        }
-        for (i, b) in stack.iter().rev().enumerate() {
-            let mut byte = *b;
-            if i != stack.len() - 1 {
-                byte |= 0x80;
-            }
-            tag_bytes.push(byte);
-        }
-    }
-    // Length encoding
-    if obj.length < 128 {
-        length_bytes.push(obj.length as u8);
-    } else {
-        let mut len = obj.length;
-        let mut len_bytes = vec![];
-        while len > 0 {
-            len_bytes.push((len & 0xFF) as u8);
-            len >>= 8;
-        }
-        len_bytes.reverse();
-        length_bytes.push(0x80 | (len_bytes.len() as u8));
-        length_bytes.extend(len_bytes);
-    }
-    // Value bytes
-    match &obj.value {
-        OwnedValue::Primitive(bytes) => value_bytes.extend(bytes),
-        OwnedValue::Constructed(children) => {
-            for child in children {
-                let (t, l, v) = get_tag_length_value_bytes(child);
-                value_bytes.extend(t);
-                value_bytes.extend(l);
-                value_bytes.extend(v);
+/// Renders a TLV as a classic hex dump: an offset column starting at
+/// `base_offset`, 16 bytes per row colored by tag (cyan), length (white) and
+/// value (green), and an ASCII gutter.
+pub fn hex_dump_lines(
+    tag_bytes: &[u8],
+    length_bytes: &[u8],
+    value_bytes: &[u8],
+    base_offset: usize,
+) -> Vec<Line<'static>> {
+    let styled: Vec<(u8, Style)> = tag_bytes
+        .iter()
+        .map(|&b| (b, Style::default().fg(Color::Cyan)))
+        .chain(
+            length_bytes
+                .iter()
+                .map(|&b| (b, Style::default().fg(Color::White))),
+        )
+        .chain(
+            value_bytes
+                .iter()
+                .map(|&b| (b, Style::default().fg(Color::Green))),
+        )
+        .collect();
+    dump_rows(&styled, base_offset)
+}
+
+/// Renders `rows` of a hex dump of the input buffer, with the bytes in
+/// `highlight` (the selected node) shown in reverse video. Only the rows
+/// asked for are built, so a large buffer costs no more per frame than the
+/// panel shows.
+pub fn buffer_dump_lines(
+    buffer: &[u8],
+    highlight: Option<std::ops::Range<usize>>,
+    rows: std::ops::Range<usize>,
+) -> Vec<Line<'static>> {
+    let highlight = highlight.unwrap_or(0..0);
+    let start = (rows.start * HEX_ROW_BYTES).min(buffer.len());
+    let end = (rows.end * HEX_ROW_BYTES).clamp(start, buffer.len());
+    let styled: Vec<(u8, Style)> = buffer[start..end]
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            let i = start + i;
+            let style = if highlight.contains(&i) {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            (b, style)
+        })
+        .collect();
+    dump_rows(&styled, start)
+}
+
+/// Lays out styled bytes as hex dump rows: offset, hex bytes in two groups of
+/// eight, and the ASCII gutter.
+fn dump_rows(styled: &[(u8, Style)], base_offset: usize) -> Vec<Line<'static>> {
+    styled
+        .chunks(HEX_ROW_BYTES)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let mut spans = vec![Span::styled(
+                format!("{:08X}  ", base_offset + row * HEX_ROW_BYTES),
+                Style::default().fg(Color::DarkGray),
+            )];
+            for (i, &(byte, style)) in chunk.iter().enumerate() {
+                let gap = if i == HEX_ROW_BYTES / 2 - 1 {
+                    "  "
+                } else {
+                    " "
+                };
+                spans.push(Span::styled(format!("{:02X}{}", byte, gap), style));
             }
-        }
+            let missing = HEX_ROW_BYTES - chunk.len();
+            let padding = missing * 3 + usize::from(chunk.len() < HEX_ROW_BYTES / 2);
+            let ascii: String = chunk
+                .iter()
+                .map(|&(b, _)| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            spans.push(Span::raw(format!("{}|{}|", " ".repeat(padding), ascii)));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Formats bytes for the clipboard: space-separated uppercase hex, or
+/// standard base64.
+pub fn clipboard_text(bytes: &[u8], format: ClipboardFormat) -> String {
+    match format {
+        ClipboardFormat::Hex => bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" "),
+        ClipboardFormat::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
     }
-    (tag_bytes, length_bytes, value_bytes)
 }
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -345,3 +702,207 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(vertical);
     horizontal_layout[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_hex_dump_rows() {
+        let value: Vec<u8> = (b'A'..b'A' + 40).collect();
+        let lines = hex_dump_lines(&[0x04], &[0x28], &value, 0x10);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            line_text(&lines[0]),
+            "00000010  04 28 41 42 43 44 45 46  47 48 49 4A 4B 4C 4D 4E |.(ABCDEFGHIJKLMN|"
+        );
+        assert_eq!(
+            line_text(&lines[2]),
+            format!(
+                "00000030  5F 60 61 62 63 64 65 66  67 68 {}|_`abcdefgh|",
+                " ".repeat(18)
+            )
+        );
+        // Every row's ASCII gutter starts in the same column.
+        let gutter: Vec<usize> = lines
+            .iter()
+            .map(|l| line_text(l).find('|').unwrap())
+            .collect();
+        assert_eq!(gutter, vec![59, 59, 59]);
+    }
+
+    #[test]
+    fn test_reconstructed_high_tag_numbers() {
+        use crate::der_parser::{DerParser, OwnedObject, OwnedValue, Tag, TagClass};
+        // The last number of each 7-bit group count and the first of the next.
+        let cases: [(u32, &[u8]); 5] = [
+            (31, &[0x1F]),
+            (127, &[0x7F]),
+            (128, &[0x81, 0x00]),
+            (16383, &[0xFF, 0x7F]),
+            (16384, &[0x81, 0x80, 0x00]),
+        ];
+        for (number, groups) in cases {
+            for (class, constructed, first) in [
+                (TagClass::ContextSpecific, false, 0x9F),
+                (TagClass::ContextSpecific, true, 0xBF),
+                (TagClass::Application, false, 0x5F),
+                (TagClass::Private, true, 0xFF),
+            ] {
+                let tag = Tag {
+                    class,
+                    constructed,
+                    number,
+                };
+                let obj = OwnedObject {
+                    tag: tag.clone(),
+                    length: if constructed { 0 } else { 1 },
+                    value: if constructed {
+                        OwnedValue::Constructed(Vec::new())
+                    } else {
+                        OwnedValue::Primitive(vec![0x2A])
+                    },
+                    tlv_start: 0,
+                    tlv_end: 0,
+                    wrapped: None,
+                };
+                let (tag_bytes, _, _) = crate::der_encoder::tlv_bytes(&obj);
+                assert_eq!(tag_bytes, [&[first][..], groups].concat(), "{:?}", tag);
+                let mut parser = DerParser::new(&tag_bytes);
+                assert_eq!(parser.read_tag(), Some(tag));
+                assert!(parser.is_done());
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_dump_colors_segments() {
+        let lines = hex_dump_lines(&[0x02], &[0x01], &[0x05], 0);
+        let colors: Vec<_> = lines[0].spans[1..4].iter().map(|s| s.style.fg).collect();
+        assert_eq!(
+            colors,
+            vec![Some(Color::Cyan), Some(Color::White), Some(Color::Green)]
+        );
+    }
+
+    #[test]
+    fn test_buffer_dump_highlights_selected_node() {
+        // SEQUENCE { INTEGER 1, INTEGER 2 }
+        let mut app = crate::tui::tree::tests::app_from_hex("3006020101020102");
+        app.selected_path = vec![0, 1];
+        assert_eq!(app.selected_buffer_range(), Some(5..8));
+
+        let lines = buffer_dump_lines(&app.buffer, app.selected_buffer_range(), 0..10);
+        assert_eq!(lines.len(), 1);
+        let highlighted: Vec<bool> = lines[0].spans[1..9]
+            .iter()
+            .map(|s| s.style.add_modifier.contains(Modifier::REVERSED))
+            .collect();
+        assert_eq!(
+            highlighted,
+            vec![false, false, false, false, false, true, true, true]
+        );
+
+        // Only the requested rows are built, with their real offsets.
+        let buffer: Vec<u8> = (0..40).collect();
+        let lines = buffer_dump_lines(&buffer, Some(17..18), 1..2);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans[0].content, "00000010  ");
+        assert!(
+            lines[0].spans[2]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+        assert!(
+            !lines[0].spans[1]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+
+        // Edited nodes have no offsets left to highlight.
+        app.selected_path = vec![0, 0];
+        app.delete_selected();
+        app.selected_path = vec![0];
+        assert_eq!(app.selected_buffer_range(), None);
+    }
+
+    #[test]
+    fn test_input_lines_mark_cursor() {
+        let reversed = |line: &Line| -> Vec<String> {
+            line.spans
+                .iter()
+                .filter(|s| s.style.add_modifier.contains(Modifier::REVERSED))
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+        let lines = input_lines("ab\ncd", Some(1));
+        assert_eq!(
+            lines.iter().map(line_text).collect::<Vec<_>>(),
+            ["ab", "cd"]
+        );
+        assert_eq!(reversed(&lines[0]), ["b"]);
+        assert!(reversed(&lines[1]).is_empty());
+
+        // At the end of a line the cursor is a reversed space.
+        let lines = input_lines("ab\ncd", Some(2));
+        assert_eq!(line_text(&lines[0]), "ab ");
+        assert!(reversed(&lines[1]).is_empty());
+        assert_eq!(reversed(&input_lines("ab\ncd", Some(5))[1]), [" "]);
+        assert!(
+            input_lines("ab", None)
+                .iter()
+                .all(|l| reversed(l).is_empty())
+        );
+    }
+
+    #[test]
+    fn test_tree_title_names_document_kind() {
+        let mut app = App::new();
+        app.parsed_objects = vec![crate::x509::tests::minimal_certificate()];
+        assert_eq!(
+            app.tree_title(),
+            "ASN.1 Tree View — Looks like: X.509 Certificate"
+        );
+        app.search_buffer = "INT".to_string();
+        assert_eq!(
+            app.tree_title(),
+            "ASN.1 Tree View /INT — Looks like: X.509 Certificate"
+        );
+        let app = crate::tui::tree::tests::app_from_hex("3003020105");
+        assert_eq!(app.tree_title(), "ASN.1 Tree View");
+    }
+
+    #[test]
+    fn test_clipboard_text_formats() {
+        let bytes = [0x30, 0x03, 0x02, 0x01, 0x05];
+        assert_eq!(
+            clipboard_text(&bytes, ClipboardFormat::Hex),
+            "30 03 02 01 05"
+        );
+        assert_eq!(clipboard_text(&bytes, ClipboardFormat::Base64), "MAMCAQU=");
+        assert_eq!(clipboard_text(&[], ClipboardFormat::Hex), "");
+    }
+
+    #[test]
+    fn test_selected_tlv_bytes_are_copied_verbatim() {
+        // A non-minimal long-form length that re-encoding would shorten.
+        let mut app = crate::tui::tree::tests::app_from_hex("308103020105");
+        let obj = app.parsed_objects[0].clone();
+        assert_eq!(app.selected_tlv_bytes(&obj), app.buffer);
+        // Without usable offsets (e.g. after an edit) the node is re-encoded.
+        app.buffer.clear();
+        assert_eq!(
+            app.selected_tlv_bytes(&obj),
+            hex::decode("3003020105").unwrap()
+        );
+    }
+}
@@ -0,0 +1,395 @@
+// src/validate.rs
+
+use crate::der_encoder::{encode_length, encode_tag, to_der};
+use crate::der_parser::{OwnedObject, OwnedValue, Tag, TagClass};
+
+/// A DER canonical-form rule broken by the node at `path`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Violation {
+    pub path: Vec<usize>,
+    pub message: String,
+}
+
+/// Walks the tree and reports every DER canonical-form violation: primitive
+/// vs constructed form, BOOLEAN, INTEGER, NULL, PrintableString and
+/// GeneralizedTime content, unsorted SET elements, and (for nodes that still
+/// carry source offsets) non-minimal headers.
+pub fn validate_der(objects: &[OwnedObject]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for (i, obj) in objects.iter().enumerate() {
+        validate_object(obj, &mut vec![i], &mut violations);
+    }
+    violations
+}
+
+fn validate_object(obj: &OwnedObject, path: &mut Vec<usize>, out: &mut Vec<Violation>) {
+    let mut report = |message: String| {
+        out.push(Violation {
+            path: path.clone(),
+            message,
+        })
+    };
+    if let Some(warning) = obj.tag.form_warning() {
+        report(warning);
+    }
+    if let Some(warning) = constructed_string_warning(&obj.tag) {
+        report(warning);
+    }
+    if let Some(warning) = header_warning(obj) {
+        report(warning);
+    }
+    match &obj.value {
+        OwnedValue::Primitive(bytes) => {
+            if let Some(warning) = primitive_warning(&obj.tag, bytes) {
+                report(warning);
+            }
+        }
+        OwnedValue::Constructed(children) => {
+            if obj.tag.class == TagClass::Universal && obj.tag.number == 17 {
+                let encodings: Vec<Vec<u8>> = children.iter().map(to_der).collect();
+                if let Some(i) = (1..encodings.len()).find(|&i| encodings[i] < encodings[i - 1]) {
+                    report(format!(
+                        "SET elements are not sorted by encoding (element {} sorts before element {})",
+                        i,
+                        i - 1
+                    ));
+                }
+            }
+            for (i, child) in children.iter().enumerate() {
+                path.push(i);
+                validate_object(child, path, out);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// DER forbids the constructed form for string types, including UTCTime and
+/// GeneralizedTime, which are encoded as VisibleStrings.
+fn constructed_string_warning(tag: &Tag) -> Option<String> {
+    let is_string = matches!(tag.number, 3 | 4 | 12 | 18..=30);
+    (tag.class == TagClass::Universal && tag.constructed && is_string).then(|| {
+        format!(
+            "string type {} must use the primitive form in DER",
+            tag.number
+        )
+    })
+}
+
+/// Compares the tag and length octets as they appeared in the source with
+/// their minimal encoding. Nodes without source offsets (e.g. edited ones)
+/// are skipped.
+fn header_warning(obj: &OwnedObject) -> Option<String> {
+    let span = obj.tlv_end.checked_sub(obj.tlv_start).filter(|&s| s > 0)?;
+    let header = span.checked_sub(obj.length)?;
+    let minimal = encode_tag(&obj.tag).len() + encode_length(obj.length).len();
+    (header > minimal).then(|| "tag or length is not in minimal (definite) form".to_string())
+}
+
+/// Checks the content octets of a primitive value against the DER rules for
+/// its universal type. Violations are reported as warnings rather than parse
+/// errors so malformed data can still be inspected.
+pub fn primitive_warning(tag: &Tag, bytes: &[u8]) -> Option<String> {
+    if tag.class != TagClass::Universal || tag.constructed {
+        return None;
+    }
+    let name = crate::tui::tree::tag_name(&tag.class, tag.number).unwrap_or_default();
+    match (tag.number, bytes) {
+        (1, [0x00] | [0xFF]) => None,
+        (1, [b]) => Some(format!(
+            "non-canonical BOOLEAN 0x{:02X} (DER requires 0x00 or 0xFF)",
+            b
+        )),
+        (1, _) => Some(format!(
+            "BOOLEAN must be exactly one byte, found {}",
+            bytes.len()
+        )),
+        (2 | 10, []) => Some(format!("{} has no content bytes", name)),
+        (5, [_, ..]) => Some(format!(
+            "NULL must have no content bytes, found {}",
+            bytes.len()
+        )),
+        (2 | 10, [0x00, next, ..]) if next & 0x80 == 0 => {
+            Some(format!("non-minimal {} (redundant leading 0x00)", name))
+        }
+        (2 | 10, [0xFF, next, ..]) if next & 0x80 != 0 => {
+            Some(format!("non-minimal {} (redundant leading 0xFF)", name))
+        }
+        (19, _) => bytes
+            .iter()
+            .find(|&&b| !crate::decode::is_printable_char(b as char))
+            .map(|&b| {
+                format!(
+                    "PrintableString contains {:?} (0x{:02X}), which is outside its alphabet",
+                    b as char, b
+                )
+            }),
+        (24, _) => generalized_time_warning(bytes),
+        _ => None,
+    }
+}
+
+/// DER allows only one spelling of a GeneralizedTime: in UTC (`Z`), with a
+/// `.` before any fractional seconds, and no trailing zeros in the fraction.
+/// Values that do not parse as a time at all are left to the display, which
+/// already shows them as invalid.
+fn generalized_time_warning(bytes: &[u8]) -> Option<String> {
+    crate::asn1_time::parse_asn1_time(bytes, true)?;
+    let text = std::str::from_utf8(bytes).ok()?;
+    let Some(body) = text.strip_suffix('Z') else {
+        let zone = &text[text.len() - 5..];
+        return Some(format!(
+            "GeneralizedTime has offset {} (DER requires UTC with a trailing Z)",
+            zone
+        ));
+    };
+    let i = body.find(['.', ','])?;
+    let fraction = &body[i..];
+    if fraction.starts_with(',') {
+        Some("GeneralizedTime uses ',' before its fraction (DER requires '.')".to_string())
+    } else if fraction.ends_with('0') {
+        Some(format!(
+            "GeneralizedTime fraction {:?} has trailing zeros",
+            fraction
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boolean() -> Tag {
+        Tag {
+            class: TagClass::Universal,
+            constructed: false,
+            number: 1,
+        }
+    }
+
+    fn parse(hex_input: &str) -> Vec<OwnedObject> {
+        crate::der_parser::parse_owned(&hex::decode(hex_input).unwrap()).unwrap()
+    }
+
+    fn messages(hex_input: &str) -> Vec<(Vec<usize>, String)> {
+        validate_der(&parse(hex_input))
+            .into_iter()
+            .map(|v| (v.path, v.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_non_minimal_integer() {
+        // SEQUENCE { INTEGER 00 7F, INTEGER FF 80, INTEGER 00 80 }
+        assert_eq!(
+            messages("300C0202007F0202FF8002020080"),
+            vec![
+                (
+                    vec![0, 0],
+                    "non-minimal INTEGER (redundant leading 0x00)".to_string()
+                ),
+                (
+                    vec![0, 1],
+                    "non-minimal INTEGER (redundant leading 0xFF)".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enumerated_is_named_in_messages() {
+        assert_eq!(
+            messages("0A00"),
+            vec![(vec![0], "ENUMERATED has no content bytes".to_string())]
+        );
+        assert_eq!(
+            messages("0A020001"),
+            vec![(
+                vec![0],
+                "non-minimal ENUMERATED (redundant leading 0x00)".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_integer_leading_byte_rules() {
+        let integer = Tag {
+            class: TagClass::Universal,
+            constructed: false,
+            number: 2,
+        };
+        // 255 needs its leading 0x00 to stay positive, so 00 FF is minimal.
+        assert_eq!(primitive_warning(&integer, &[0x00, 0xFF]), None);
+        assert_eq!(primitive_warning(&integer, &[0x7F]), None);
+        assert_eq!(
+            primitive_warning(&integer, &[0x00, 0x7F]).as_deref(),
+            Some("non-minimal INTEGER (redundant leading 0x00)")
+        );
+    }
+
+    #[test]
+    fn test_unsorted_set() {
+        // SET { INTEGER 2, INTEGER 1 }
+        assert_eq!(
+            messages("3106020102020101"),
+            vec![(
+                vec![0],
+                "SET elements are not sorted by encoding (element 1 sorts before element 0)"
+                    .to_string()
+            )]
+        );
+        assert!(messages("3106020101020102").is_empty());
+    }
+
+    #[test]
+    fn test_non_minimal_length_and_constructed_string() {
+        // OCTET STRING with a long-form length for a 1-byte value.
+        assert_eq!(
+            messages("04810100"),
+            vec![(
+                vec![0],
+                "tag or length is not in minimal (definite) form".to_string()
+            )]
+        );
+        // Constructed OCTET STRING { OCTET STRING 00 }
+        assert_eq!(
+            messages("2403040100"),
+            vec![(
+                vec![0],
+                "string type 4 must use the primitive form in DER".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_null_with_content() {
+        assert_eq!(
+            messages("050100"),
+            vec![(
+                vec![0],
+                "NULL must have no content bytes, found 1".to_string()
+            )]
+        );
+        assert!(messages("0500").is_empty());
+    }
+
+    #[test]
+    fn test_form_violations() {
+        // A primitive SEQUENCE and a constructed INTEGER { INTEGER 1 }.
+        assert_eq!(
+            messages("10002203020101"),
+            vec![
+                (vec![0], "SEQUENCE encoded as primitive".to_string()),
+                (vec![1], "INTEGER encoded as constructed".to_string()),
+            ]
+        );
+        // Constructed UTCTime { UTCTime "" }
+        assert_eq!(
+            messages("37021700"),
+            vec![(
+                vec![0],
+                "string type 23 must use the primitive form in DER".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_valid_certificate_has_no_violations() {
+        let der = crate::der_parser::try_decode_input(include_str!("../testCert.pem")).unwrap();
+        let objects = crate::der_parser::parse_owned(&der).unwrap();
+        assert_eq!(validate_der(&objects), vec![]);
+    }
+
+    #[test]
+    fn test_printable_string_alphabet() {
+        let printable = Tag {
+            class: TagClass::Universal,
+            constructed: false,
+            number: 19,
+        };
+        assert_eq!(
+            primitive_warning(&printable, b"Example Corp. (Test) 1+1=2?"),
+            None
+        );
+        assert_eq!(
+            primitive_warning(&printable, b"admin@example.com").as_deref(),
+            Some("PrintableString contains '@' (0x40), which is outside its alphabet")
+        );
+        // Valid UTF-8 is still outside the alphabet.
+        assert_eq!(
+            primitive_warning(&printable, "é".as_bytes()).as_deref(),
+            Some("PrintableString contains 'Ã' (0xC3), which is outside its alphabet")
+        );
+    }
+
+    #[test]
+    fn test_printable_string_violation_still_shows_text() {
+        // PrintableString "a_b"
+        let objects = parse("1303615F62");
+        let output = crate::format::render_tree_string(&objects, &Default::default());
+        assert!(output.contains("'a_b'"), "{output}");
+        assert!(
+            output.contains("⚠ PrintableString contains '_' (0x5F), which is outside its alphabet"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_canonical_booleans() {
+        assert_eq!(primitive_warning(&boolean(), &[0x00]), None);
+        assert_eq!(primitive_warning(&boolean(), &[0xFF]), None);
+    }
+
+    #[test]
+    fn test_non_canonical_booleans() {
+        assert_eq!(
+            primitive_warning(&boolean(), &[0x01]).as_deref(),
+            Some("non-canonical BOOLEAN 0x01 (DER requires 0x00 or 0xFF)")
+        );
+        assert_eq!(
+            primitive_warning(&boolean(), &[]).as_deref(),
+            Some("BOOLEAN must be exactly one byte, found 0")
+        );
+        assert_eq!(
+            primitive_warning(&boolean(), &[0xFF, 0xFF]).as_deref(),
+            Some("BOOLEAN must be exactly one byte, found 2")
+        );
+    }
+
+    #[test]
+    fn test_generalized_time_canonical_form() {
+        let time = Tag {
+            class: TagClass::Universal,
+            constructed: false,
+            number: 24,
+        };
+        assert_eq!(primitive_warning(&time, b"20230101000000Z"), None);
+        assert_eq!(primitive_warning(&time, b"20230101000000.5Z"), None);
+        assert_eq!(
+            primitive_warning(&time, b"20230101000000.000Z").as_deref(),
+            Some("GeneralizedTime fraction \".000\" has trailing zeros")
+        );
+        assert_eq!(
+            primitive_warning(&time, b"20230101000000,5Z").as_deref(),
+            Some("GeneralizedTime uses ',' before its fraction (DER requires '.')")
+        );
+        assert_eq!(
+            primitive_warning(&time, b"20230101010000+0100").as_deref(),
+            Some("GeneralizedTime has offset +0100 (DER requires UTC with a trailing Z)")
+        );
+        // Not a time at all: the display already says so.
+        assert_eq!(primitive_warning(&time, b"yesterday"), None);
+    }
+
+    #[test]
+    fn test_non_canonical_generalized_time_still_shows_normalized_value() {
+        // GeneralizedTime "20230101000000.000Z"
+        let objects = parse("181332303233303130313030303030302E3030305A");
+        let output = crate::format::render_tree_string(&objects, &Default::default());
+        assert!(output.contains("→ 2023-01-01T00:00:00Z"), "{output}");
+        assert!(output.contains("⚠ GeneralizedTime fraction"), "{output}");
+        assert_eq!(validate_der(&objects).len(), 1);
+    }
+}
@@ -0,0 +1,533 @@
+// src/x509.rs
+
+use crate::asn1_time::{Asn1Time, parse_asn1_time_with_pivot};
+use crate::der_parser::{OwnedObject, OwnedValue, TagClass};
+use crate::schema::{ANY, Schema};
+use std::collections::HashMap;
+
+fn children(obj: &OwnedObject) -> Option<&[OwnedObject]> {
+    match &obj.value {
+        OwnedValue::Constructed(children) => Some(children),
+        OwnedValue::Primitive(_) => None,
+    }
+}
+
+fn is_universal(obj: &OwnedObject, number: u32) -> bool {
+    obj.tag.class == TagClass::Universal && obj.tag.number == number
+}
+
+fn is_time(obj: &OwnedObject) -> bool {
+    is_universal(obj, 23) || is_universal(obj, 24)
+}
+
+/// Returns the tbsCertificate of `obj` if it has the outer shape of an X.509
+/// Certificate: SEQUENCE { tbsCertificate SEQUENCE, signatureAlgorithm SEQUENCE,
+/// signatureValue BIT STRING }.
+pub fn tbs_certificate(obj: &OwnedObject) -> Option<&OwnedObject> {
+    let parts = children(obj).filter(|_| is_universal(obj, 16))?;
+    match parts {
+        [tbs, alg, sig, ..]
+            if is_universal(tbs, 16) && is_universal(alg, 16) && is_universal(sig, 3) =>
+        {
+            Some(tbs)
+        }
+        _ => None,
+    }
+}
+
+/// Guesses the kind of document held in `objects` from the outer shape of
+/// the first top-level object: an X.509 Certificate, a PKCS#10 certificate
+/// request or a PKCS#8 PrivateKeyInfo. The match is purely structural, so
+/// unrelated data of the same shape is misnamed rather than rejected.
+pub fn document_kind(objects: &[OwnedObject]) -> Option<&'static str> {
+    let obj = objects.first()?;
+    if let Some(info) = tbs_certificate(obj) {
+        // CertificationRequestInfo: version, subject, subjectPKInfo, [0] attributes.
+        return match children(info) {
+            Some([version, subject, spki, attributes])
+                if is_universal(version, 2)
+                    && is_universal(subject, 16)
+                    && is_universal(spki, 16)
+                    && attributes.tag.class == TagClass::ContextSpecific
+                    && attributes.tag.number == 0 =>
+            {
+                Some("PKCS#10 Certificate Request")
+            }
+            _ => Some("X.509 Certificate"),
+        };
+    }
+    // PrivateKeyInfo: version, privateKeyAlgorithm, privateKey OCTET STRING, ...
+    match children(obj).filter(|_| is_universal(obj, 16))? {
+        [version, algorithm, key, ..]
+            if is_universal(version, 2) && is_universal(algorithm, 16) && is_universal(key, 4) =>
+        {
+            Some("PKCS#8 Private Key")
+        }
+        _ => None,
+    }
+}
+
+/// Collects friendly field names for well-known positions in any
+/// certificate-shaped top-level objects, keyed by tree path.
+pub fn field_labels(objects: &[OwnedObject]) -> HashMap<Vec<usize>, &'static str> {
+    let mut labels = HashMap::new();
+    for (i, obj) in objects.iter().enumerate() {
+        let Some(fields) = tbs_certificate(obj).and_then(children) else {
+            continue;
+        };
+        // The version field is an optional EXPLICIT [0] in front of serialNumber.
+        let offset = match fields.first() {
+            Some(first)
+                if first.tag.class == TagClass::ContextSpecific && first.tag.number == 0 =>
+            {
+                1
+            }
+            _ => 0,
+        };
+        let validity_idx = offset + 3;
+        let Some(validity) = fields.get(validity_idx) else {
+            continue;
+        };
+        if let Some([not_before, not_after]) = children(validity)
+            && is_universal(validity, 16)
+            && is_time(not_before)
+            && is_time(not_after)
+        {
+            labels.insert(vec![i, 0, validity_idx, 0], "notBefore");
+            labels.insert(vec![i, 0, validity_idx, 1], "notAfter");
+        }
+    }
+    labels
+}
+
+/// Field names of an X.509 v2/v3 Certificate (RFC 5280). The fields after
+/// subjectPublicKeyInfo are optional, so only the common v3 layout, with
+/// extensions straight after the key, is named. notBefore and notAfter are
+/// left to [`field_labels`], which also finds them in v1 certificates.
+pub const CERTIFICATE_SCHEMA: Schema = Schema {
+    applies_to: has_explicit_version,
+    fields: &[
+        (&[0], "tbsCertificate"),
+        (&[0, 0], "version"),
+        (&[0, 1], "serialNumber"),
+        (&[0, 2], "signature"),
+        (&[0, 3], "issuer"),
+        (&[0, 4], "validity"),
+        (&[0, 5], "subject"),
+        (&[0, 6], "subjectPublicKeyInfo"),
+        (&[0, 6, 0], "algorithm"),
+        (&[0, 6, 1], "subjectPublicKey"),
+        (&[0, 7], "extensions"),
+        (&[0, 7, 0, ANY], "extension"),
+        (&[0, 7, 0, ANY, 0], "extnID"),
+        (&[1], "signatureAlgorithm"),
+        (&[2], "signatureValue"),
+    ],
+};
+
+/// A certificate whose tbsCertificate starts with the `[0]` version, so the
+/// positions in [`CERTIFICATE_SCHEMA`] line up.
+fn has_explicit_version(obj: &OwnedObject) -> bool {
+    tbs_certificate(obj)
+        .and_then(children)
+        .and_then(|fields| fields.first())
+        .is_some_and(|first| first.tag.class == TagClass::ContextSpecific && first.tag.number == 0)
+}
+
+/// The short attribute keys used in string Distinguished Names (RFC 4514).
+fn attribute_short_name(dotted: &str) -> Option<&'static str> {
+    let name = match dotted {
+        "2.5.4.3" => "CN",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.9" => "STREET",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Decodes an attribute value if it is one of the string types used in
+/// DirectoryString and friends.
+fn attribute_text(obj: &OwnedObject) -> Option<String> {
+    let OwnedValue::Primitive(bytes) = &obj.value else {
+        return None;
+    };
+    if obj.tag.class != TagClass::Universal {
+        return None;
+    }
+    match obj.tag.number {
+        12 | 19 | 20 | 22 => std::str::from_utf8(bytes).ok().map(str::to_string),
+        18 | 21 | 25..=28 => crate::decode::decode_restricted_string(obj.tag.number, bytes),
+        30 => crate::decode::decode_bmp_string(bytes),
+        _ => None,
+    }
+}
+
+/// Renders a RelativeDistinguishedName, a SET of AttributeTypeAndValue
+/// SEQUENCEs (an OID followed by a string), as e.g. `CN=example.com`.
+/// Multi-valued RDNs are joined with `+`; attribute types without a short
+/// key fall back to their OID name or dotted form.
+pub fn rdn_label(obj: &OwnedObject) -> Option<String> {
+    let attributes = children(obj).filter(|c| is_universal(obj, 17) && !c.is_empty())?;
+    let parts = attributes
+        .iter()
+        .map(|atv| {
+            let [oid, value] = children(atv).filter(|_| is_universal(atv, 16))? else {
+                return None;
+            };
+            let OwnedValue::Primitive(oid_bytes) = &oid.value else {
+                return None;
+            };
+            let dotted = is_universal(oid, 6)
+                .then(|| crate::oid::decode_oid(oid_bytes).ok())
+                .flatten()?;
+            let key = attribute_short_name(&dotted)
+                .or_else(|| crate::oid::oid_name(&dotted))
+                .map_or(dotted.clone(), str::to_string);
+            Some(format!("{}={}", key, attribute_text(value)?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("+"))
+}
+
+/// Finds every Name-shaped SEQUENCE (a SEQUENCE whose children are all
+/// RDN SETs) and maps the path of each RDN to its [`rdn_label`].
+pub fn rdn_labels(objects: &[OwnedObject]) -> HashMap<Vec<usize>, String> {
+    fn walk(obj: &OwnedObject, path: &mut Vec<usize>, out: &mut HashMap<Vec<usize>, String>) {
+        let Some(kids) = children(obj) else {
+            return;
+        };
+        if is_universal(obj, 16) && !kids.is_empty() {
+            let labels: Option<Vec<String>> = kids.iter().map(rdn_label).collect();
+            for (i, label) in labels.into_iter().flatten().enumerate() {
+                path.push(i);
+                out.insert(path.clone(), label);
+                path.pop();
+            }
+        }
+        for (i, child) in kids.iter().enumerate() {
+            path.push(i);
+            walk(child, path, out);
+            path.pop();
+        }
+    }
+    let mut labels = HashMap::new();
+    for (i, obj) in objects.iter().enumerate() {
+        walk(obj, &mut vec![i], &mut labels);
+    }
+    labels
+}
+
+/// Names the algorithm of an AlgorithmIdentifier-shaped SEQUENCE: a known
+/// signature or key algorithm OID optionally followed by parameters.
+pub fn algorithm_identifier(obj: &OwnedObject) -> Option<&'static str> {
+    let ([oid] | [oid, _]) = children(obj).filter(|_| is_universal(obj, 16))? else {
+        return None;
+    };
+    let OwnedValue::Primitive(oid_bytes) = &oid.value else {
+        return None;
+    };
+    if !is_universal(oid, 6) {
+        return None;
+    }
+    crate::oid::algorithm_name(&crate::oid::decode_oid(oid_bytes).ok()?)
+}
+
+/// Maps the path of every SEQUENCE that [`algorithm_identifier`] names to
+/// that algorithm.
+pub fn algorithm_labels(objects: &[OwnedObject]) -> HashMap<Vec<usize>, &'static str> {
+    fn walk(obj: &OwnedObject, path: &mut Vec<usize>, out: &mut HashMap<Vec<usize>, &'static str>) {
+        if let Some(name) = algorithm_identifier(obj) {
+            out.insert(path.clone(), name);
+        }
+        for (i, child) in children(obj).unwrap_or_default().iter().enumerate() {
+            path.push(i);
+            walk(child, path, out);
+            path.pop();
+        }
+    }
+    let mut labels = HashMap::new();
+    for (i, obj) in objects.iter().enumerate() {
+        walk(obj, &mut vec![i], &mut labels);
+    }
+    labels
+}
+
+/// Whether the certificate is within its validity window at `now` (Unix
+/// seconds), phrased for the given Validity field.
+fn validity_status(field: &str, time: &Asn1Time, now: i64) -> &'static str {
+    match field {
+        "notBefore" if now < time.unix_seconds() => " (not yet valid)",
+        "notAfter" if now > time.unix_seconds() => " (expired)",
+        "notAfter" => " (valid)",
+        _ => "",
+    }
+}
+
+/// Renders a Validity time in ISO-8601 along with its validity status.
+pub fn validity_annotation(field: &str, time: &Asn1Time, now: i64) -> String {
+    format!("{}{}", time.to_iso8601(), validity_status(field, time, now))
+}
+
+/// Decodes a Validity time node and returns just its status suffix, e.g.
+/// `" (expired)"`; `None` if the value is malformed. UTCTime years follow
+/// `pivot` (`--utc-year-pivot`), as the displayed date does.
+pub fn describe_validity(
+    field: &str,
+    obj: &OwnedObject,
+    now: i64,
+    pivot: u32,
+) -> Option<&'static str> {
+    let OwnedValue::Primitive(bytes) = &obj.value else {
+        return None;
+    };
+    let time = parse_asn1_time_with_pivot(bytes, obj.tag.number == 24, pivot)?;
+    Some(validity_status(field, &time, now))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::asn1_time::{RFC5280_YEAR_PIVOT, parse_asn1_time};
+    use crate::der_parser::Tag;
+
+    pub(crate) fn universal(number: u32, value: OwnedValue) -> OwnedObject {
+        let mut obj = OwnedObject {
+            tag: Tag {
+                class: TagClass::Universal,
+                constructed: matches!(value, OwnedValue::Constructed(_)),
+                number,
+            },
+            length: 0,
+            value,
+            tlv_start: 0,
+            tlv_end: 0,
+            wrapped: None,
+        };
+        obj.length = crate::der_encoder::encode_content(&obj).len();
+        obj
+    }
+
+    /// A certificate-shaped tree without the optional version field.
+    pub(crate) fn minimal_certificate() -> OwnedObject {
+        let validity = universal(
+            16,
+            OwnedValue::Constructed(vec![
+                universal(23, OwnedValue::Primitive(b"230115120000Z".to_vec())),
+                universal(24, OwnedValue::Primitive(b"20500101000000Z".to_vec())),
+            ]),
+        );
+        let tbs = universal(
+            16,
+            OwnedValue::Constructed(vec![
+                universal(2, OwnedValue::Primitive(vec![0x01])),
+                universal(16, OwnedValue::Constructed(vec![])),
+                universal(16, OwnedValue::Constructed(vec![])),
+                validity,
+            ]),
+        );
+        universal(
+            16,
+            OwnedValue::Constructed(vec![
+                tbs,
+                universal(16, OwnedValue::Constructed(vec![])),
+                universal(3, OwnedValue::Primitive(vec![0x00])),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_field_labels_for_validity() {
+        let labels = field_labels(&[minimal_certificate()]);
+        assert_eq!(labels.get(&vec![0, 0, 3, 0]), Some(&"notBefore"));
+        assert_eq!(labels.get(&vec![0, 0, 3, 1]), Some(&"notAfter"));
+        assert_eq!(labels.len(), 2);
+    }
+
+    /// SEQUENCE { SET { SEQUENCE { OID 2.5.4.3, UTF8String "example.com" } } }
+    fn common_name_rdn() -> OwnedObject {
+        let atv = universal(
+            16,
+            OwnedValue::Constructed(vec![
+                universal(6, OwnedValue::Primitive(vec![0x55, 0x04, 0x03])),
+                universal(12, OwnedValue::Primitive(b"example.com".to_vec())),
+            ]),
+        );
+        universal(
+            16,
+            OwnedValue::Constructed(vec![universal(17, OwnedValue::Constructed(vec![atv]))]),
+        )
+    }
+
+    #[test]
+    fn test_rdn_labels_for_common_name() {
+        let name = common_name_rdn();
+        let labels = rdn_labels(std::slice::from_ref(&name));
+        assert_eq!(
+            labels.get(&vec![0, 0]).map(String::as_str),
+            Some("CN=example.com")
+        );
+        assert_eq!(labels.len(), 1);
+
+        // A SET holding anything but OID/string pairs is left alone.
+        let OwnedValue::Constructed(rdns) = &name.value else {
+            unreachable!()
+        };
+        let mut set = rdns[0].clone();
+        let OwnedValue::Constructed(atvs) = &mut set.value else {
+            unreachable!()
+        };
+        atvs.push(universal(2, OwnedValue::Primitive(vec![0x01])));
+        assert_eq!(rdn_label(&set), None);
+    }
+
+    #[test]
+    fn test_multi_valued_rdn_label() {
+        let atv = |oid: Vec<u8>, number: u32, text: &[u8]| {
+            universal(
+                16,
+                OwnedValue::Constructed(vec![
+                    universal(6, OwnedValue::Primitive(oid)),
+                    universal(number, OwnedValue::Primitive(text.to_vec())),
+                ]),
+            )
+        };
+        let set = universal(
+            17,
+            OwnedValue::Constructed(vec![
+                atv(vec![0x55, 0x04, 0x0B], 19, b"Sales"),
+                atv(vec![0x55, 0x04, 0x2A], 30, &[0x00, b'A', 0x00, b'l']),
+            ]),
+        );
+        assert_eq!(rdn_label(&set).as_deref(), Some("OU=Sales+givenName=Al"));
+    }
+
+    #[test]
+    fn test_algorithm_identifier() {
+        let algorithm = |oid: Vec<u8>, params: Option<OwnedObject>| {
+            let mut fields = vec![universal(6, OwnedValue::Primitive(oid))];
+            fields.extend(params);
+            universal(16, OwnedValue::Constructed(fields))
+        };
+        // ecdsa-with-SHA256 has no parameters; sha256WithRSAEncryption has NULL.
+        let ecdsa = vec![0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02];
+        let rsa = vec![0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B];
+        let null = universal(5, OwnedValue::Primitive(vec![]));
+        assert_eq!(
+            algorithm_identifier(&algorithm(ecdsa.clone(), None)),
+            Some("ecdsa-with-SHA256")
+        );
+        assert_eq!(
+            algorithm_identifier(&algorithm(rsa, Some(null))),
+            Some("sha256WithRSAEncryption")
+        );
+        // ecPublicKey with its curve as parameters.
+        let curve = universal(
+            6,
+            OwnedValue::Primitive(vec![0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07]),
+        );
+        let key = algorithm(vec![0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01], Some(curve));
+        assert_eq!(algorithm_identifier(&key), Some("ecPublicKey"));
+
+        // Named OIDs that aren't algorithms, like commonName, are left alone.
+        assert_eq!(
+            algorithm_identifier(&algorithm(vec![0x55, 0x04, 0x03], None)),
+            None
+        );
+
+        let spki = universal(
+            16,
+            OwnedValue::Constructed(vec![
+                algorithm(ecdsa, None),
+                universal(3, OwnedValue::Primitive(vec![0x00])),
+            ]),
+        );
+        let labels = algorithm_labels(&[spki]);
+        assert_eq!(labels.get(&vec![0, 0]), Some(&"ecdsa-with-SHA256"));
+        assert_eq!(labels.len(), 1);
+    }
+
+    #[test]
+    fn test_document_kind() {
+        assert_eq!(
+            document_kind(&[minimal_certificate()]),
+            Some("X.509 Certificate")
+        );
+        let parse_pem = |pem: &str| {
+            crate::der_parser::parse_owned(&crate::der_parser::try_decode_input(pem).unwrap())
+                .unwrap()
+        };
+        assert_eq!(
+            document_kind(&parse_pem(include_str!("../testCert.pem"))),
+            Some("X.509 Certificate")
+        );
+        assert_eq!(
+            document_kind(&parse_pem(include_str!("../testCSR.pem"))),
+            Some("PKCS#10 Certificate Request")
+        );
+        assert_eq!(
+            document_kind(&parse_pem(include_str!("../testKey2.pem"))),
+            Some("PKCS#8 Private Key")
+        );
+    }
+
+    #[test]
+    fn test_document_kind_ignores_other_shapes() {
+        // SEQUENCE { INTEGER 5, BOOLEAN true, SEQUENCE { NULL } }
+        let objects =
+            crate::der_parser::parse_owned(&hex::decode("300A0201050101FF30020500").unwrap())
+                .unwrap();
+        assert_eq!(document_kind(&objects), None);
+        assert_eq!(document_kind(&[]), None);
+    }
+
+    #[test]
+    fn test_field_labels_ignore_non_certificates() {
+        let seq = universal(16, OwnedValue::Constructed(vec![]));
+        assert!(field_labels(&[seq]).is_empty());
+    }
+
+    #[test]
+    fn test_validity_annotation_expiry() {
+        let time = parse_asn1_time(b"230115120000Z", false).unwrap();
+        let before = time.unix_seconds() - 1;
+        let after = time.unix_seconds() + 1;
+        assert_eq!(
+            validity_annotation("notAfter", &time, after),
+            "2023-01-15T12:00:00Z (expired)"
+        );
+        assert_eq!(
+            validity_annotation("notAfter", &time, before),
+            "2023-01-15T12:00:00Z (valid)"
+        );
+        assert_eq!(
+            validity_annotation("notBefore", &time, before),
+            "2023-01-15T12:00:00Z (not yet valid)"
+        );
+        assert_eq!(
+            validity_annotation("notBefore", &time, after),
+            "2023-01-15T12:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_validity_status_follows_year_pivot() {
+        // notAfter 2049-01-01 under RFC 5280, 1949-01-01 with a pivot of 40.
+        let not_after = universal(23, OwnedValue::Primitive(b"490101000000Z".to_vec()));
+        let now = parse_asn1_time(b"20240101000000Z", true)
+            .unwrap()
+            .unix_seconds();
+        assert_eq!(
+            describe_validity("notAfter", &not_after, now, RFC5280_YEAR_PIVOT),
+            Some(" (valid)")
+        );
+        assert_eq!(
+            describe_validity("notAfter", &not_after, now, 40),
+            Some(" (expired)")
+        );
+    }
+}
@@ -0,0 +1,252 @@
+// tests/cli.rs
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Writes `contents` to a file in the system temp dir that is unique to this
+/// test process.
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("asn1smith-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn missing_file_exits_with_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .arg("/nonexistent/asn1smith-input.der")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot read /nonexistent/asn1smith-input.der"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn unparseable_file_exits_with_error() {
+    // A SEQUENCE claiming five content bytes but holding only two.
+    let path = temp_file("truncated.der", &[0x30, 0x05, 0x02, 0x01]);
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot parse"), "{stderr}");
+    assert!(
+        stderr.contains("unexpected end of input at offset 1"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn dump_prints_tree_without_tui() {
+    // SEQUENCE { INTEGER 5, NULL }
+    let path = temp_file("dump.der", &[0x30, 0x05, 0x02, 0x01, 0x05, 0x05, 0x00]);
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .args(["--dump", "--no-color"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("SEQUENCE"), "{stdout}");
+    assert!(stdout.contains("INTEGER: 5"), "{stdout}");
+    assert!(!stdout.contains('\x1b'), "{stdout}");
+}
+
+#[test]
+fn dump_reads_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .arg("-d")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"3003020105")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("SEQUENCE"), "{stdout}");
+}
+
+#[test]
+fn dump_honors_no_color_env() {
+    let path = temp_file("no-color.der", &[0x30, 0x03, 0x01, 0x01, 0xFF]);
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .arg("--dump")
+        .arg(&path)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    assert!(!output.stdout.contains(&0x1b), "{:?}", output.stdout);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("BOOLEAN: true"));
+
+    // Colors are on by default.
+    let path = temp_file("color.der", &[0x05, 0x00]);
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .arg("--dump")
+        .arg(&path)
+        .env_remove("NO_COLOR")
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.stdout.contains(&0x1b));
+}
+
+#[test]
+fn piped_stdin_is_dumped_without_flag() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("NO_COLOR", "1")
+        .spawn()
+        .unwrap();
+    // Raw DER, not text: SEQUENCE { BOOLEAN true }
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(&[0x30, 0x03, 0x01, 0x01, 0xFF])
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("SEQUENCE"), "{stdout}");
+    assert!(stdout.contains("BOOLEAN: true"), "{stdout}");
+}
+
+#[test]
+fn dump_caps_long_values() {
+    let mut der = vec![0x04, 0x0A];
+    der.extend(0u8..10);
+    let path = temp_file("long-value.der", &der);
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .args(["--dump", "--no-color", "--max-value-bytes", "4"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("OCTET STRING: (10 bytes): [00, 01, 02, 03] … (+6 more)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn dump_applies_utc_year_pivot() {
+    let mut der = vec![0x17, 0x0D];
+    der.extend(b"500101000000Z");
+    let path = temp_file("utc-pivot.der", &der);
+    let dump = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+            .args(["--dump", "--no-color"])
+            .args(extra)
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let default = dump(&[]);
+    let pivoted = dump(&["--utc-year-pivot", "60"]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(default.contains("→ 1950-01-01T00:00:00Z"), "{default}");
+    assert!(pivoted.contains("→ 2050-01-01T00:00:00Z"), "{pivoted}");
+}
+
+#[test]
+fn dump_applies_indent_width() {
+    // SEQUENCE { INTEGER 5 }
+    let path = temp_file("indent.der", &[0x30, 0x03, 0x02, 0x01, 0x05]);
+    let dump = |width: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+            .args(["--dump", "--no-color", "--indent", width])
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let four = dump("4");
+    let tabs = dump("tab");
+    std::fs::remove_file(&path).unwrap();
+    assert!(four.contains("\n        INTEGER: 5 "), "{four}");
+    assert!(tabs.contains("\n\t\tINTEGER: 5 "), "{tabs}");
+}
+
+#[test]
+fn dump_prints_each_file_with_a_header() {
+    let first = temp_file("first.der", &[0x05, 0x00]);
+    let second = temp_file("second.der", &[0x02, 0x01, 0x07]);
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .args(["--dump", "--no-color"])
+        .arg(&first)
+        .arg(&second)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&first).unwrap();
+    std::fs::remove_file(&second).unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_header = format!("==> {} <==", first.display());
+    let second_header = format!("==> {} <==", second.display());
+    let null = stdout.find("NULL").unwrap();
+    let integer = stdout.find("INTEGER: 7").unwrap();
+    assert!(stdout.starts_with(&first_header), "{stdout}");
+    assert!(null < stdout.find(&second_header).unwrap(), "{stdout}");
+    assert!(stdout.find(&second_header).unwrap() < integer, "{stdout}");
+}
+
+#[test]
+fn unknown_option_is_a_usage_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+        .args(["--dmup", "cert.pem"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown option --dmup"), "{stderr}");
+    assert!(stderr.contains("usage: asn1smith"), "{stderr}");
+    assert!(!stderr.contains("cannot read"), "{stderr}");
+}
+
+#[test]
+fn invalid_env_settings_are_rejected() {
+    let cases = [
+        ("ASN1SMITH_POLL_MS", "0"),
+        ("ASN1SMITH_POLL_MS", "fast"),
+        ("ASN1SMITH_VALUE_WIDTH", "0"),
+        ("ASN1SMITH_COLLAPSE_DEPTH", "-1"),
+    ];
+    for (name, value) in cases {
+        let output = Command::new(env!("CARGO_BIN_EXE_asn1smith"))
+            .arg("/nonexistent/asn1smith-input.der")
+            .env(name, value)
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{name}={value}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(&format!("{name} expects")), "{stderr}");
+    }
+}